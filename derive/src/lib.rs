@@ -0,0 +1,279 @@
+#![doc = "Derive macros for the assoc_static crate, use them through its 'derive' feature."]
+#![warn(missing_docs)]
+
+use proc_macro::{Delimiter, Group, Spacing, TokenStream, TokenTree};
+
+/// The parts of an item declaration the derives are interested in.
+struct Item {
+    kind: String,
+    name: String,
+    generics: bool,
+    /// Attributes as (name, tokens inside the parenthesis)
+    attrs: Vec<(String, TokenStream)>,
+    /// The parenthesized or braced body, `None` for unit structs
+    body: Option<Group>,
+}
+
+impl Item {
+    fn parse(input: TokenStream) -> Result<Item, String> {
+        let mut tokens = input.into_iter().peekable();
+        let mut attrs = Vec::new();
+        while let Some(token) = tokens.next() {
+            match token {
+                TokenTree::Punct(punct) if punct.as_char() == '#' => {
+                    if let Some(TokenTree::Group(group)) = tokens.next() {
+                        let mut attr = group.stream().into_iter();
+                        if let Some(TokenTree::Ident(name)) = attr.next() {
+                            let args = match attr.next() {
+                                Some(TokenTree::Group(args))
+                                    if args.delimiter() == Delimiter::Parenthesis =>
+                                {
+                                    args.stream()
+                                }
+                                _ => TokenStream::new(),
+                            };
+                            attrs.push((name.to_string(), args));
+                        }
+                    }
+                }
+                TokenTree::Ident(ident) if ident.to_string() == "pub" => {
+                    if let Some(TokenTree::Group(_)) = tokens.peek() {
+                        tokens.next();
+                    }
+                }
+                TokenTree::Ident(ident) => {
+                    let kind = ident.to_string();
+                    let name = match tokens.next() {
+                        Some(TokenTree::Ident(name)) => name.to_string(),
+                        _ => return Err(format!("expected a name after '{kind}'")),
+                    };
+                    let generics =
+                        matches!(tokens.peek(), Some(TokenTree::Punct(p)) if p.as_char() == '<');
+                    let body = tokens.find_map(|token| match token {
+                        TokenTree::Group(group) if group.delimiter() != Delimiter::Bracket => {
+                            Some(group)
+                        }
+                        _ => None,
+                    });
+                    return Ok(Item {
+                        kind,
+                        name,
+                        generics,
+                        attrs,
+                        body,
+                    });
+                }
+                _ => {}
+            }
+        }
+        Err("expected an item".to_string())
+    }
+
+    /// Returns the type of the field of a single field tuple struct.
+    fn newtype_field(&self) -> Result<TokenStream, String> {
+        let error = || format!("'{}' must be a tuple struct with one field", self.name);
+        if self.kind != "struct" {
+            return Err(error());
+        }
+        let body = match &self.body {
+            Some(body) if body.delimiter() == Delimiter::Parenthesis => body,
+            _ => return Err(error()),
+        };
+        let mut fields = split_commas(body.stream());
+        if fields.len() != 1 {
+            return Err(error());
+        }
+        Ok(strip_attrs_and_vis(fields.remove(0)))
+    }
+
+    /// Returns the arguments of all attributes named 'name'.
+    fn attrs<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a TokenStream> + 'a {
+        self.attrs
+            .iter()
+            .filter(move |(attr, _)| attr == name)
+            .map(|(_, args)| args)
+    }
+}
+
+/// Splits a token stream at commas which are not nested in angle brackets.
+/// Empty parts (from trailing commas) are dropped.
+fn split_commas(stream: TokenStream) -> Vec<TokenStream> {
+    let mut parts = Vec::new();
+    let mut current = Vec::new();
+    let mut depth = 0usize;
+    let mut arrow = false;
+    for token in stream {
+        if let TokenTree::Punct(punct) = &token {
+            match punct.as_char() {
+                '<' => depth += 1,
+                '>' if !arrow => depth = depth.saturating_sub(1),
+                ',' if depth == 0 => {
+                    parts.push(current.drain(..).collect());
+                    continue;
+                }
+                _ => {}
+            }
+            arrow = punct.as_char() == '-' && punct.spacing() == Spacing::Joint;
+        } else {
+            arrow = false;
+        }
+        current.push(token);
+    }
+    if !current.is_empty() {
+        parts.push(current.into_iter().collect());
+    }
+    parts
+}
+
+/// Removes leading attributes and visibility from a field.
+fn strip_attrs_and_vis(field: TokenStream) -> TokenStream {
+    let mut tokens = field.into_iter().peekable();
+    loop {
+        match tokens.peek() {
+            Some(TokenTree::Punct(punct)) if punct.as_char() == '#' => {
+                tokens.next();
+                tokens.next();
+            }
+            Some(TokenTree::Ident(ident)) if ident.to_string() == "pub" => {
+                tokens.next();
+                if let Some(TokenTree::Group(_)) = tokens.peek() {
+                    tokens.next();
+                }
+            }
+            _ => return tokens.collect(),
+        }
+    }
+}
+
+fn compile_error(message: &str) -> TokenStream {
+    format!("::core::compile_error!({message:?});")
+        .parse()
+        .expect("valid compile_error")
+}
+
+/// Forwards associations from the inner type of a newtype to the newtype.
+///
+/// The associations to forward are listed in `#[assoc_delegate(...)]` attributes as 'TARGET'
+/// or 'TAG:TARGET', separated by commas. Generic newtypes are not supported.
+#[proc_macro_derive(AssocDelegate, attributes(assoc_delegate))]
+pub fn derive_assoc_delegate(input: TokenStream) -> TokenStream {
+    let item = match Item::parse(input) {
+        Ok(item) => item,
+        Err(message) => return compile_error(&message),
+    };
+    if item.generics {
+        return compile_error("AssocDelegate does not support generic types");
+    }
+    let inner = match item.newtype_field() {
+        Ok(inner) => inner,
+        Err(message) => return compile_error(&message),
+    };
+    item.attrs("assoc_delegate")
+        .map(|args| {
+            format!(
+                "::assoc_static::assoc_delegate!({} => {inner}; {args});",
+                item.name
+            )
+            .parse::<TokenStream>()
+            .expect("valid assoc_delegate invocation")
+        })
+        .collect()
+}
+
+/// Associates a lazily created `Default::default()` instance of the type with itself under
+/// the `DefaultsTag`. The type must be `Sync + Send`. Generic types are not supported.
+#[proc_macro_derive(AssocDefault)]
+pub fn derive_assoc_default(input: TokenStream) -> TokenStream {
+    let item = match Item::parse(input) {
+        Ok(item) => item,
+        Err(message) => return compile_error(&message),
+    };
+    if item.generics {
+        return compile_error("AssocDefault does not support generic types");
+    }
+    format!(
+        "::assoc_static::assoc_static!(::assoc_static::DefaultsTag: {name}, {name} = \
+         <{name} as ::core::default::Default>::default(); boxed);",
+        name = item.name
+    )
+    .parse()
+    .expect("valid assoc_static invocation")
+}
+
+/// Associates the `MessageInfo` given in the `#[assoc_message(...)]` attribute as
+/// 'magic = ..., id = ..., min_len = ..., max_len = ...' with a wire message type. 'min_len'
+/// defaults to zero and 'max_len' to `u32::MAX`. Generic types are not supported.
+#[proc_macro_derive(AssocMessage, attributes(assoc_message))]
+pub fn derive_assoc_message(input: TokenStream) -> TokenStream {
+    let item = match Item::parse(input) {
+        Ok(item) => item,
+        Err(message) => return compile_error(&message),
+    };
+    if item.generics {
+        return compile_error("AssocMessage does not support generic types");
+    }
+    let mut fields = vec![
+        ("magic", None),
+        ("id", None),
+        ("min_len", Some("0".to_string())),
+        ("max_len", Some("u32::MAX".to_string())),
+    ];
+    for args in item.attrs("assoc_message") {
+        for arg in split_commas(args.clone()) {
+            let mut tokens = arg.into_iter();
+            let name = match (tokens.next(), tokens.next()) {
+                (Some(TokenTree::Ident(name)), Some(TokenTree::Punct(eq)))
+                    if eq.as_char() == '=' =>
+                {
+                    name.to_string()
+                }
+                _ => return compile_error("expected 'name = value' in assoc_message"),
+            };
+            match fields.iter_mut().find(|(field, _)| *field == name) {
+                Some((_, value)) => *value = Some(tokens.collect::<TokenStream>().to_string()),
+                None => return compile_error(&format!("unknown assoc_message field '{name}'")),
+            }
+        }
+    }
+    let mut info = String::new();
+    for (field, value) in fields {
+        match value {
+            Some(value) => info.push_str(&format!("{field}: {value}, ")),
+            None => return compile_error(&format!("assoc_message requires '{field}'")),
+        }
+    }
+    format!(
+        "::assoc_static::assoc_static!(::assoc_static::MessageTag: {name}, \
+         ::assoc_static::MessageInfo = ::assoc_static::MessageInfo {{ {info} }});",
+        name = item.name
+    )
+    .parse()
+    .expect("valid assoc_static invocation")
+}
+
+/// Runs a test function isolated by the `Snapshots` set given as argument, as in
+/// `#[assoc_test(STATE)]`. The objects of the set are reset before the test body and
+/// restored afterwards, see `Snapshots::isolate()`. This replaces the `#[test]` attribute.
+#[proc_macro_attribute]
+pub fn assoc_test(args: TokenStream, input: TokenStream) -> TokenStream {
+    if args.is_empty() {
+        return compile_error("assoc_test requires a Snapshots set, as in #[assoc_test(STATE)]");
+    }
+    let mut tokens: Vec<TokenTree> = input.into_iter().collect();
+    let body = match tokens.pop() {
+        Some(TokenTree::Group(body)) if body.delimiter() == Delimiter::Brace => body,
+        _ => return compile_error("assoc_test expects a function"),
+    };
+    let mut closure: TokenStream = "||".parse().expect("valid closure");
+    closure.extend([TokenTree::Group(body)]);
+    let mut call = args;
+    call.extend(".isolate".parse::<TokenStream>().expect("valid method"));
+    call.extend([TokenTree::Group(Group::new(
+        Delimiter::Parenthesis,
+        closure,
+    ))]);
+    let mut test: TokenStream = "#[test]".parse().expect("valid attribute");
+    test.extend(tokens);
+    test.extend([TokenTree::Group(Group::new(Delimiter::Brace, call))]);
+    test
+}
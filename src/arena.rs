@@ -0,0 +1,114 @@
+use std::sync::Mutex;
+
+use crate::AssocStatic;
+
+crate::tags! {
+    /// Tag for the `Arena` of a type, associated with `assoc_arena!()`.
+    pub ArenaTag;
+}
+
+/// An append only typed arena. Values are stored in chunks that never move, they are
+/// dropped only when the arena is dropped, thus never when it is associated with a type.
+///
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Node {
+///     value: u32,
+///     next: Option<&'static Node>,
+/// }
+/// assoc_arena!(Node);
+///
+/// let arena = Node::arena();
+/// let tail = arena.alloc(Node { value: 2, next: None });
+/// let head = arena.alloc(Node { value: 1, next: Some(tail) });
+/// assert_eq!(head.next.unwrap().value, 2);
+/// assert_eq!(arena.len(), 2);
+/// ```
+#[derive(Debug)]
+pub struct Arena<T> {
+    chunks: Mutex<Vec<Vec<T>>>,
+}
+
+impl<T> Arena<T> {
+    const FIRST_CHUNK: usize = 16;
+
+    /// Creates an empty arena, no memory is allocated until the first value is stored.
+    pub const fn new() -> Self {
+        Arena {
+            chunks: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Returns the arena associated with 'X'.
+    pub fn of<X: AssocStatic<Self, ArenaTag> + ?Sized>() -> &'static Self {
+        X::get_static()
+    }
+
+    /// Moves 'value' into the arena.
+    pub fn alloc(&self, value: T) -> &T {
+        let mut chunks = self.chunks.lock().unwrap_or_else(|err| err.into_inner());
+        let full = chunks
+            .last()
+            .is_none_or(|chunk| chunk.len() == chunk.capacity());
+        if full {
+            let capacity = chunks
+                .last()
+                .map_or(Self::FIRST_CHUNK, |chunk| chunk.capacity() * 2);
+            chunks.push(Vec::with_capacity(capacity));
+        }
+        let chunk = chunks.last_mut().expect("chunk was added");
+        chunk.push(value);
+        let value: *const T = chunk.last().expect("value was pushed");
+        // SAFETY: a chunk is never pushed beyond its capacity, thus never reallocated, and
+        // values are not removed before the arena is dropped.
+        unsafe { &*value }
+    }
+
+    /// Returns the number of values in the arena.
+    pub fn len(&self) -> usize {
+        let chunks = self.chunks.lock().unwrap_or_else(|err| err.into_inner());
+        chunks.iter().map(Vec::len).sum()
+    }
+
+    /// Returns true when the arena holds no values.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> Default for Arena<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Associates an `Arena` of the type with the type itself and generates an `arena()`
+/// accessor on it.
+#[macro_export]
+macro_rules! assoc_arena {
+    ($T:ty) => {
+        $crate::assoc_static!($crate::ArenaTag:$T, $crate::Arena<$T> = $crate::Arena::new());
+
+        impl $T {
+            /// Returns the arena values of this type are allocated in.
+            #[allow(dead_code)]
+            pub fn arena() -> &'static $crate::Arena<$T> {
+                $crate::Arena::<$T>::of::<$T>()
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Arena;
+
+    #[test]
+    fn stable_references() {
+        let arena = Arena::new();
+        let values: Vec<&usize> = (0..100).map(|n| arena.alloc(n)).collect();
+        assert!(values.iter().enumerate().all(|(n, value)| **value == n));
+        assert_eq!(arena.len(), 100);
+    }
+}
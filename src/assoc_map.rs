@@ -0,0 +1,146 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::{OnceLock, PoisonError, RwLock};
+
+use crate::AssocStatic;
+
+/// A concurrent map that can be used as associated static object.
+/// The underlying `HashMap` is built lazily on first access.
+///
+/// Values are never dropped, they are leaked to hand out `'static` references. This map is
+/// meant for per-type caches that live for the whole program lifetime.
+pub struct StaticMap<K, V: 'static> {
+    map: OnceLock<RwLock<HashMap<K, &'static V>>>,
+}
+
+impl<K, V> StaticMap<K, V> {
+    /// Creates an empty map, usable as initializer in `assoc_static!()`.
+    pub const fn new() -> Self {
+        StaticMap {
+            map: OnceLock::new(),
+        }
+    }
+
+    fn map(&self) -> &RwLock<HashMap<K, &'static V>> {
+        self.map.get_or_init(|| RwLock::new(HashMap::new()))
+    }
+}
+
+impl<K, V> Default for StaticMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Hash + Eq, V> StaticMap<K, V> {
+    /// Returns a reference to the value stored under 'key'.
+    pub fn get<Q>(&self, key: &Q) -> Option<&'static V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map()
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(key)
+            .copied()
+    }
+
+    /// Returns a reference to the value stored under 'key', calling 'init' to create it when
+    /// not present. 'init' runs without holding any lock. When two threads race to insert the
+    /// same key, the first one wins and the other value is dropped.
+    pub fn get_or_insert_with(&self, key: K, init: impl FnOnce() -> V) -> &'static V {
+        if let Some(value) = self.get(&key) {
+            return value;
+        }
+        let value = init();
+        let mut map = self.map().write().unwrap_or_else(PoisonError::into_inner);
+        if let Some(value) = map.get(&key) {
+            return value;
+        }
+        let value = Box::leak(Box::new(value));
+        map.insert(key, value);
+        value
+    }
+
+    /// Returns true when a value is stored under 'key'.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.get(key).is_some()
+    }
+
+    /// Returns the number of entries in the map.
+    pub fn len(&self) -> usize {
+        self.map()
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+
+    /// Returns true when the map has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Gives access to an associated `StaticMap`, keyed by runtime data.
+/// Implemented for every type that has a `StaticMap<K, V>` associated with `assoc_static!()`.
+///
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Example;
+/// assoc_static!(Example, StaticMap<String, usize> = StaticMap::new());
+///
+/// let len = Example::assoc_map().get_or_insert_with("hello".to_string(), || "hello".len());
+/// assert_eq!(*len, 5);
+/// assert_eq!(Example::assoc_map().get("hello"), Some(&5));
+/// assert_eq!(Example::assoc_map().get("world"), None);
+/// ```
+pub trait AssocMap<K, V: 'static, TAG = ()> {
+    /// Returns a reference to the associated map of the Self type
+    fn assoc_map() -> &'static StaticMap<K, V>;
+}
+
+impl<T, K, V: 'static, TAG> AssocMap<K, V, TAG> for T
+where
+    T: AssocStatic<StaticMap<K, V>, TAG> + ?Sized,
+{
+    fn assoc_map() -> &'static StaticMap<K, V> {
+        T::get_static()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{assoc_static, AssocMap, StaticMap};
+
+    struct TestType;
+    assoc_static!(TestType, StaticMap<u32, String> = StaticMap::new());
+
+    struct OtherTag;
+    assoc_static!(OtherTag:TestType, StaticMap<u32, String> = StaticMap::new());
+
+    #[test]
+    fn get_or_insert() {
+        let map = <TestType as AssocMap<u32, String>>::assoc_map();
+        assert!(map.is_empty());
+        assert_eq!(map.get_or_insert_with(1, || "one".to_string()), "one");
+        assert_eq!(map.get_or_insert_with(1, || "uno".to_string()), "one");
+        assert_eq!(map.get(&1).map(String::as_str), Some("one"));
+        assert!(!map.contains_key(&2));
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn tagged() {
+        let map = <TestType as AssocMap<u32, String, OtherTag>>::assoc_map();
+        map.get_or_insert_with(7, || "seven".to_string());
+        assert!(map.contains_key(&7));
+        assert!(!<TestType as AssocMap<u32, String>>::assoc_map().contains_key(&7));
+    }
+}
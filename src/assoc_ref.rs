@@ -0,0 +1,112 @@
+use std::any::TypeId;
+use std::cell::RefCell;
+use std::marker::PhantomData;
+
+use crate::AssocStatic;
+
+thread_local! {
+    static SUBSTITUTES: RefCell<Vec<(TypeId, *const ())>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Pops the substitute pushed by `AssocRef::substitute()`, even when the closure panics.
+struct SubstituteGuard;
+
+impl Drop for SubstituteGuard {
+    fn drop(&mut self) {
+        SUBSTITUTES.with(|substitutes| substitutes.borrow_mut().pop());
+    }
+}
+
+/// Scoped access to an associated object. By default this resolves to the associated static
+/// object, but it can be substituted by a borrowed value for the duration of a closure on
+/// the current thread. Implemented for every type that implements `AssocStatic`.
+///
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Example;
+/// assoc_static!(Example, String = String::new());
+///
+/// assert_eq!(Example::with_ref(|s| s.len()), 0);
+///
+/// let local = String::from("temporary");
+/// Example::substitute(&local, || {
+///     assert_eq!(Example::with_ref(|s| s.clone()), "temporary");
+/// });
+///
+/// assert_eq!(Example::with_ref(|s| s.len()), 0);
+/// ```
+pub trait AssocRef<T: 'static, TAG: 'static = ()>: AssocStatic<T, TAG> + 'static {
+    /// Calls 'f' with the innermost substitute of the current thread or the associated
+    /// static object when there is none.
+    fn with_ref<R>(f: impl FnOnce(&T) -> R) -> R {
+        let id = TypeId::of::<(T, TAG, PhantomData<Self>)>();
+        let substitute = SUBSTITUTES.with(|substitutes| {
+            substitutes
+                .borrow()
+                .iter()
+                .rev()
+                .find(|(key, _)| *key == id)
+                .map(|(_, ptr)| *ptr)
+        });
+        match substitute {
+            // SAFETY: the pointer was created from a '&T' in 'substitute()' which outlives
+            // the entry on the stack, 'f' is called within that scope.
+            Some(ptr) => f(unsafe { &*(ptr as *const T) }),
+            None => f(Self::get_static()),
+        }
+    }
+
+    /// Substitutes the associated object with 'value' while 'f' runs on the current thread.
+    /// Substitutions nest, the innermost one wins.
+    fn substitute<R>(value: &T, f: impl FnOnce() -> R) -> R {
+        let id = TypeId::of::<(T, TAG, PhantomData<Self>)>();
+        SUBSTITUTES.with(|substitutes| {
+            substitutes
+                .borrow_mut()
+                .push((id, value as *const T as *const ()))
+        });
+        let _guard = SubstituteGuard;
+        f()
+    }
+}
+
+impl<X: AssocStatic<T, TAG> + 'static + ?Sized, T: 'static, TAG: 'static> AssocRef<T, TAG> for X {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{assoc_static, AssocRef};
+
+    struct TestType;
+    assoc_static!(TestType, u32 = 1);
+
+    struct TestTag;
+    assoc_static!(TestTag:TestType, u32 = 10);
+
+    fn current() -> u32 {
+        <TestType as AssocRef<u32>>::with_ref(|v| *v)
+    }
+
+    #[test]
+    fn nested() {
+        assert_eq!(current(), 1);
+        <TestType as AssocRef<u32>>::substitute(&2, || {
+            assert_eq!(current(), 2);
+            <TestType as AssocRef<u32>>::substitute(&3, || {
+                assert_eq!(current(), 3);
+            });
+            assert_eq!(current(), 2);
+            assert_eq!(<TestType as AssocRef<u32, TestTag>>::with_ref(|v| *v), 10);
+        });
+        assert_eq!(current(), 1);
+    }
+
+    #[test]
+    fn panic_unwinds_substitute() {
+        let result = std::panic::catch_unwind(|| {
+            <TestType as AssocRef<u32>>::substitute(&5, || panic!("substituted"));
+        });
+        assert!(result.is_err());
+        assert_eq!(current(), 1);
+    }
+}
@@ -0,0 +1,186 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::sync::Mutex;
+
+use crate::AssocStatic;
+
+crate::tags! {
+    /// Tag for the `Cache` of a type, associated with `assoc_cache!()`.
+    pub CacheTag;
+}
+
+/// A least recently used cache, associated per type with `assoc_cache!()`.
+///
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Schema;
+/// assoc_cache!(Schema, String => usize, 2);
+///
+/// let cache = Schema::cache();
+/// assert_eq!(cache.get_or_insert_with("a".into(), || 1), 1);
+/// assert_eq!(cache.get_or_insert_with("a".into(), || 2), 1);
+/// cache.insert("b".into(), 2);
+/// cache.insert("c".into(), 3);
+/// assert_eq!(cache.get(&"a".into()), None);
+/// assert_eq!(cache.len(), 2);
+/// ```
+#[derive(Debug)]
+pub struct Cache<K, V> {
+    capacity: usize,
+    entries: Mutex<Option<Entries<K, V>>>,
+}
+
+#[derive(Debug)]
+struct Entries<K, V> {
+    map: HashMap<K, (V, u64)>,
+    order: BTreeMap<u64, K>,
+    tick: u64,
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Entries<K, V> {
+    fn touch(&mut self, key: &K) -> Option<V> {
+        let tick = self.tick;
+        let (value, used) = self.map.get_mut(key)?;
+        self.order.remove(used);
+        *used = tick;
+        self.order.insert(tick, key.clone());
+        self.tick += 1;
+        Some(value.clone())
+    }
+
+    fn insert(&mut self, key: K, value: V, capacity: usize) {
+        if let Some((_, used)) = self.map.remove(&key) {
+            self.order.remove(&used);
+        }
+        while self.map.len() >= capacity {
+            match self.order.pop_first() {
+                Some((_, oldest)) => self.map.remove(&oldest),
+                None => return,
+            };
+        }
+        self.order.insert(self.tick, key.clone());
+        self.map.insert(key, (value, self.tick));
+        self.tick += 1;
+    }
+}
+
+impl<K, V> Cache<K, V> {
+    /// Creates an empty cache holding at most 'capacity' entries.
+    pub const fn new(capacity: usize) -> Self {
+        Cache {
+            capacity,
+            entries: Mutex::new(None),
+        }
+    }
+
+    /// Returns the cache associated with 'T'.
+    pub fn of<T: AssocStatic<Self, CacheTag> + ?Sized>() -> &'static Self {
+        T::get_static()
+    }
+
+    /// Returns the maximum number of entries.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Cache<K, V> {
+    fn with<R>(&self, f: impl FnOnce(&mut Entries<K, V>) -> R) -> R {
+        let mut entries = self.entries.lock().unwrap_or_else(|err| err.into_inner());
+        f(entries.get_or_insert_with(|| Entries {
+            map: HashMap::new(),
+            order: BTreeMap::new(),
+            tick: 0,
+        }))
+    }
+
+    /// Returns a copy of the value of 'key' and marks it as recently used.
+    pub fn get(&self, key: &K) -> Option<V> {
+        self.with(|entries| entries.touch(key))
+    }
+
+    /// Inserts 'value', evicting the least recently used entry when the cache is full.
+    pub fn insert(&self, key: K, value: V) {
+        self.with(|entries| entries.insert(key, value, self.capacity));
+    }
+
+    /// Returns the value of 'key', computing and inserting it with 'f' when missing.
+    /// The cache is not locked while 'f' runs.
+    pub fn get_or_insert_with(&self, key: K, f: impl FnOnce() -> V) -> V {
+        if let Some(value) = self.get(&key) {
+            return value;
+        }
+        let value = f();
+        self.insert(key, value.clone());
+        value
+    }
+
+    /// Returns the number of entries.
+    pub fn len(&self) -> usize {
+        self.with(|entries| entries.map.len())
+    }
+
+    /// Returns true when the cache has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all entries.
+    pub fn clear(&self) {
+        self.with(|entries| {
+            entries.map.clear();
+            entries.order.clear();
+        });
+    }
+}
+
+/// Associates a `Cache` of the given capacity with a type and generates a `cache()` accessor
+/// on it, thus a type can have only one cache declared this way.
+#[macro_export]
+macro_rules! assoc_cache {
+    ($T:ty, $K:ty => $V:ty, $CAPACITY:expr) => {
+        $crate::assoc_static!(
+            $crate::CacheTag:$T,
+            $crate::Cache<$K, $V> = $crate::Cache::new($CAPACITY)
+        );
+
+        impl $T {
+            /// Returns the cache of this type.
+            #[allow(dead_code)]
+            pub fn cache() -> &'static $crate::Cache<$K, $V> {
+                $crate::Cache::<$K, $V>::of::<$T>()
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Cache;
+
+    struct TestType;
+    assoc_cache!(TestType, u32 => &'static str, 2);
+
+    #[test]
+    fn least_recently_used() {
+        let cache = TestType::cache();
+        assert!(std::ptr::eq(cache, Cache::<u32, &str>::of::<TestType>()));
+        cache.insert(1, "one");
+        cache.insert(2, "two");
+        assert_eq!(cache.get(&1), Some("one"));
+        cache.insert(3, "three");
+        assert_eq!(cache.get(&2), None);
+        assert_eq!(cache.get(&1), Some("one"));
+        assert_eq!(cache.get(&3), Some("three"));
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn zero_capacity() {
+        let cache = Cache::new(0);
+        cache.insert(1, 1);
+        assert_eq!(cache.get(&1), None);
+    }
+}
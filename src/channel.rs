@@ -0,0 +1,107 @@
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Mutex, PoisonError};
+
+crate::tags! {
+    /// Tag for the `Channel` of an event type, associated with `assoc_channel!()`.
+    pub ChannelTag;
+}
+
+/// A broadcast channel, every event sent is delivered to all current subscribers.
+/// Subscribers which dropped their receiver are removed on the next send.
+#[derive(Debug, Default)]
+pub struct Channel<E> {
+    subscribers: Mutex<Vec<Sender<E>>>,
+}
+
+impl<E: Clone> Channel<E> {
+    /// Creates a channel without subscribers.
+    pub const fn new() -> Self {
+        Channel {
+            subscribers: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Returns a receiver for all events sent from now on.
+    pub fn subscribe(&self) -> Receiver<E> {
+        let (sender, receiver) = mpsc::channel();
+        self.lock().push(sender);
+        receiver
+    }
+
+    /// Sends 'event' to all subscribers, returns the number of subscribers reached.
+    pub fn send(&self, event: E) -> usize {
+        let mut subscribers = self.lock();
+        subscribers.retain(|subscriber| subscriber.send(event.clone()).is_ok());
+        subscribers.len()
+    }
+
+    /// Returns the number of subscribers as of the last send.
+    pub fn subscribers(&self) -> usize {
+        self.lock().len()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Sender<E>>> {
+        self.subscribers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Associates a `Channel` with each of the given event types, turning them into a minimal
+/// typed event bus. The event types must be `Clone + Send` and get inherent `sender()` and
+/// `subscribe()` functions.
+///
+/// ```
+/// use crate::assoc_static::*;
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Login(&'static str);
+/// assoc_channel!(Login);
+///
+/// let events = Login::subscribe();
+/// assert_eq!(Login::sender().send(Login("alice")), 1);
+/// assert_eq!(events.recv(), Ok(Login("alice")));
+/// ```
+#[macro_export]
+macro_rules! assoc_channel {
+    ($($E:ty),+ $(,)?) => {
+        $(
+            $crate::assoc_static!(
+                $crate::ChannelTag:$E,
+                $crate::Channel<$E> = $crate::Channel::new()
+            );
+
+            impl $E {
+                /// Returns the channel events of this type are sent on.
+                #[allow(dead_code)]
+                pub fn sender() -> &'static $crate::Channel<$E> {
+                    <$E as $crate::AssocStatic<_, $crate::ChannelTag>>::get_static()
+                }
+
+                /// Returns a receiver for all events of this type sent from now on.
+                #[allow(dead_code)]
+                pub fn subscribe() -> std::sync::mpsc::Receiver<$E> {
+                    Self::sender().subscribe()
+                }
+            }
+        )+
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[derive(Clone, Debug, PartialEq)]
+    struct Tick(u32);
+    assoc_channel!(Tick);
+
+    #[test]
+    fn broadcast() {
+        let first = Tick::subscribe();
+        let second = Tick::subscribe();
+        assert_eq!(Tick::sender().send(Tick(1)), 2);
+        drop(second);
+        assert_eq!(Tick::sender().send(Tick(2)), 1);
+        assert_eq!(first.try_iter().collect::<Vec<_>>(), [Tick(1), Tick(2)]);
+        assert_eq!(Tick::sender().subscribers(), 1);
+    }
+}
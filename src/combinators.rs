@@ -0,0 +1,61 @@
+use crate::AssocStatic;
+
+/// Calls 'f' with 'value' and its associated static object. The tag is the first type
+/// parameter, so it can be given as `with_assoc::<TAG, _, _, _>`.
+///
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Example(u32);
+/// struct Unit;
+/// assoc_static!(Unit:Example, &'static str = "km");
+///
+/// let distance = with_assoc::<Unit, _, _, _>(&Example(5), |value, unit| {
+///     format!("{} {unit}", value.0)
+/// });
+/// assert_eq!(distance, "5 km");
+/// ```
+pub fn with_assoc<TAG, T: 'static, V, R>(value: &V, f: impl FnOnce(&V, &'static T) -> R) -> R
+where
+    V: AssocStatic<T, TAG> + ?Sized,
+{
+    f(value, V::assoc(value))
+}
+
+/// Maps the associated static object of 'value' into another value.
+///
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Example;
+/// assoc_static!(Example, &'static str = "Hello World!");
+///
+/// assert_eq!(map_assoc(&Example, |s: &&str| s.len()), 12);
+/// ```
+pub fn map_assoc<TAG, T: 'static, V, R>(value: &V, f: impl FnOnce(&'static T) -> R) -> R
+where
+    V: AssocStatic<T, TAG> + ?Sized,
+{
+    f(V::assoc(value))
+}
+
+/// Pairs the associated static objects of two values with the same tag.
+///
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Name;
+/// struct Dog;
+/// struct Cat;
+/// assoc_static!(Name:Dog, &'static str = "dog");
+/// assoc_static!(Name:Cat, &'static str = "cat");
+///
+/// assert_eq!(zip_assoc::<Name, _, _, _, _>(&Dog, &Cat), (&"dog", &"cat"));
+/// ```
+pub fn zip_assoc<TAG, T: 'static, U: 'static, A, B>(a: &A, b: &B) -> (&'static T, &'static U)
+where
+    A: AssocStatic<T, TAG> + ?Sized,
+    B: AssocStatic<U, TAG> + ?Sized,
+{
+    (A::assoc(a), B::assoc(b))
+}
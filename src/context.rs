@@ -0,0 +1,118 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::AssocStatic;
+
+crate::tags! {
+    /// Tag for the `ErrorContext` of an error type.
+    pub ErrorContextTag: ErrorContext;
+}
+
+/// Static metadata describing an error type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ErrorContext {
+    /// What went wrong, shown in front of the error message
+    pub context: &'static str,
+    /// Where to find help, for example an URL to the documentation
+    pub help: Option<&'static str>,
+}
+
+/// An error together with the `ErrorContext` associated with its type. It displays only the
+/// context, the wrapped error is its `source()`, so error reporters and `anyhow`/`thiserror`
+/// chains print each of them once.
+#[derive(Debug)]
+pub struct WithContext<E> {
+    /// The associated context
+    pub context: &'static ErrorContext,
+    /// The original error
+    pub error: E,
+}
+
+impl<E> fmt::Display for WithContext<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.context.context)?;
+        if let Some(help) = self.context.help {
+            write!(f, " (see {help})")?;
+        }
+        Ok(())
+    }
+}
+
+impl<E: Error + 'static> Error for WithContext<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Attaches the `ErrorContext` associated with the error type of a `Result` to its error.
+/// A local tag makes it possible to give foreign error types a context.
+///
+/// ```
+/// use crate::assoc_static::*;
+/// use std::num::ParseIntError;
+///
+/// struct Port;
+/// assoc_static!(Port:ParseIntError, ErrorContext = ErrorContext {
+///     context: "invalid port",
+///     help: Some("https://example.com/ports"),
+/// });
+///
+/// let error = "http".parse::<u16>().context_assoc::<Port>().unwrap_err();
+/// assert_eq!(error.to_string(), "invalid port (see https://example.com/ports)");
+/// assert_eq!(error.error.to_string(), "invalid digit found in string");
+/// ```
+pub trait ContextAssoc<T, E> {
+    /// Wraps the error in a `WithContext` carrying the context associated under 'TAG'.
+    fn context_assoc<TAG>(self) -> Result<T, WithContext<E>>
+    where
+        E: AssocStatic<ErrorContext, TAG>;
+}
+
+impl<T, E> ContextAssoc<T, E> for Result<T, E> {
+    fn context_assoc<TAG>(self) -> Result<T, WithContext<E>>
+    where
+        E: AssocStatic<ErrorContext, TAG>,
+    {
+        self.map_err(|error| WithContext {
+            context: E::get_static(),
+            error,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+    use std::fmt;
+
+    use super::{ContextAssoc, ErrorContext};
+    use crate::{assoc_static, ErrorContextTag};
+
+    #[derive(Debug)]
+    struct Timeout;
+
+    impl fmt::Display for Timeout {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("timed out")
+        }
+    }
+
+    impl Error for Timeout {}
+
+    assoc_static!(ErrorContextTag:Timeout, ErrorContext = ErrorContext {
+        context: "connecting failed",
+        help: None,
+    });
+
+    #[test]
+    fn source_and_display() {
+        let result: Result<(), Timeout> = Err(Timeout);
+        let error = result.context_assoc::<ErrorContextTag>().unwrap_err();
+        assert_eq!(error.to_string(), "connecting failed");
+        assert_eq!(error.source().unwrap().to_string(), "timed out");
+        assert_eq!(
+            ErrorContextTag::of::<Timeout>().context,
+            "connecting failed"
+        );
+    }
+}
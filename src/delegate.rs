@@ -0,0 +1,131 @@
+use std::marker::PhantomData;
+
+use crate::{AssocStatic, AssocStaticRef, Descriptor};
+
+/// `PhantomData<T>` resolves to the associations of 'T', so type level tokens can stand in
+/// for values.
+///
+/// ```
+/// use crate::assoc_static::*;
+/// use std::marker::PhantomData;
+///
+/// struct Example;
+/// assoc_static!(Example, &'static str = "Example");
+///
+/// let token = PhantomData::<Example>;
+/// assert_eq!(AssocStatic::assoc(&token), &"Example");
+/// ```
+impl<T, X, TAG> AssocStatic<X, TAG> for PhantomData<T>
+where
+    T: AssocStatic<X, TAG> + ?Sized,
+{
+    fn get_static() -> &'static X {
+        T::get_static()
+    }
+
+    fn descriptor() -> Descriptor
+    where
+        Self: 'static,
+        X: 'static,
+        TAG: 'static,
+    {
+        T::descriptor()
+    }
+}
+
+impl<T, X: 'static, TAG> AssocStaticRef<X, TAG> for PhantomData<T>
+where
+    T: AssocStaticRef<X, TAG> + ?Sized,
+{
+    const STATIC_REF: &'static X = T::STATIC_REF;
+}
+
+/// `Option<T>` resolves to the associations of 'T'. The association is available even when
+/// the option is `None`.
+///
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Example;
+/// assoc_static!(Example, &'static str = "Example");
+///
+/// let maybe: Option<Example> = None;
+/// assert_eq!(AssocStatic::assoc(&maybe), &"Example");
+/// ```
+#[cfg(feature = "delegate")]
+impl<T, X, TAG> AssocStatic<X, TAG> for Option<T>
+where
+    T: AssocStatic<X, TAG>,
+{
+    fn get_static() -> &'static X {
+        T::get_static()
+    }
+
+    fn descriptor() -> Descriptor
+    where
+        Self: 'static,
+        X: 'static,
+        TAG: 'static,
+    {
+        T::descriptor()
+    }
+}
+
+#[cfg(feature = "delegate")]
+impl<T, X: 'static, TAG> AssocStaticRef<X, TAG> for Option<T>
+where
+    T: AssocStaticRef<X, TAG>,
+{
+    const STATIC_REF: &'static X = T::STATIC_REF;
+}
+
+/// `Result<T, E>` resolves to the associations of 'T'. The association is available even
+/// when the result is an error.
+#[cfg(feature = "delegate")]
+impl<T, E, X, TAG> AssocStatic<X, TAG> for Result<T, E>
+where
+    T: AssocStatic<X, TAG>,
+{
+    fn get_static() -> &'static X {
+        T::get_static()
+    }
+
+    fn descriptor() -> Descriptor
+    where
+        Self: 'static,
+        X: 'static,
+        TAG: 'static,
+    {
+        T::descriptor()
+    }
+}
+
+#[cfg(feature = "delegate")]
+impl<T, E, X: 'static, TAG> AssocStaticRef<X, TAG> for Result<T, E>
+where
+    T: AssocStaticRef<X, TAG>,
+{
+    const STATIC_REF: &'static X = T::STATIC_REF;
+}
+
+#[cfg(all(test, feature = "delegate"))]
+mod tests {
+    use crate::{assoc_static, AssocStatic};
+
+    struct TestType;
+    assoc_static!(TestType, u32 = 121);
+
+    #[test]
+    fn option() {
+        assert_eq!(*AssocStatic::<u32>::assoc(&Some(TestType)), 121);
+        assert_eq!(*AssocStatic::<u32>::assoc(&None::<TestType>), 121);
+    }
+
+    #[test]
+    fn result() {
+        let ok: Result<TestType, ()> = Ok(TestType);
+        let err: Result<TestType, ()> = Err(());
+        assert_eq!(*AssocStatic::<u32>::assoc(&ok), 121);
+        assert_eq!(*AssocStatic::<u32>::assoc(&err), 121);
+    }
+}
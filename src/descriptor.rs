@@ -0,0 +1,151 @@
+use std::any::{type_name, TypeId};
+use std::fmt;
+use std::mem::{align_of, size_of};
+
+/// Source location of an `assoc_static!()` invocation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Location {
+    /// The source file
+    pub file: &'static str,
+    /// The line in the source file
+    pub line: u32,
+    /// The column in the source file
+    pub column: u32,
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.column)
+    }
+}
+
+/// Describes an association, returned by `AssocStatic::descriptor()`.
+/// This is meant for debuggers, leak reports and other tooling that wants to know what is
+/// attached to a type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Descriptor {
+    /// Name of the type the static object is associated to
+    pub type_name: &'static str,
+    /// Name of the type of the static object
+    pub target_name: &'static str,
+    /// Name of the tag type
+    pub tag_name: &'static str,
+    /// `TypeId` of the type the static object is associated to
+    pub type_id: TypeId,
+    /// `TypeId` of the type of the static object
+    pub target_id: TypeId,
+    /// `TypeId` of the tag type
+    pub tag_id: TypeId,
+    /// `size_of` the static object
+    pub size: usize,
+    /// `align_of` the static object
+    pub align: usize,
+    /// Id of the association, see `stable_id()`
+    pub id: u64,
+    /// Where the association was declared, `None` when not declared by the macros of this
+    /// crate.
+    pub location: Option<Location>,
+}
+
+impl Descriptor {
+    /// Creates the descriptor for the association of a 'TARGET' with 'TAG' to 'T'. The id is
+    /// hashed over the `type_name`s here, `assoc_static!()` replaces it with the id computed
+    /// at compile time. This is not a const fn because `type_name` is not.
+    pub fn new<T, TARGET, TAG>(location: Option<Location>) -> Self
+    where
+        T: 'static + ?Sized,
+        TARGET: 'static,
+        TAG: 'static,
+    {
+        Descriptor {
+            id: stable_id(&[type_name::<T>(), type_name::<TAG>(), type_name::<TARGET>()]),
+            type_name: type_name::<T>(),
+            target_name: type_name::<TARGET>(),
+            tag_name: type_name::<TAG>(),
+            type_id: TypeId::of::<T>(),
+            target_id: TypeId::of::<TARGET>(),
+            tag_id: TypeId::of::<TAG>(),
+            size: size_of::<TARGET>(),
+            align: align_of::<TARGET>(),
+            location,
+        }
+    }
+}
+
+/// Computes a 64 bit FNV-1a hash over 'parts', each part followed by a `0xff` byte (which
+/// never occurs in UTF-8) as separator.
+///
+/// The id of an association is this hash over the type prefixed by `module_path!()`, the
+/// tag and the target, spelled as in the `assoc_static!()` invocation with all whitespace
+/// removed. It is computed at compile time and does not depend on the compiler. The
+/// instantiations of a `for<P in [..]>` declaration additionally append the parameter
+/// name and the argument, thus each one has its own id.
+///
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Example;
+/// assoc_static!(Example, u32 = 42);
+///
+/// let expected = stable_id(&[concat!(module_path!(), "::Example"), "()", "u32"]);
+/// assert_eq!(<Example as AssocStatic<u32>>::id(), expected);
+/// ```
+pub const fn stable_id(parts: &[&str]) -> u64 {
+    hash_parts(OFFSET, parts, false)
+}
+
+/// Computes the id of an association as described in `stable_id()`.
+#[doc(hidden)]
+pub const fn association_id(parts: [&str; 3], instance: &[&str]) -> u64 {
+    hash_parts(hash_parts(OFFSET, &parts, true), instance, true)
+}
+
+/// Glob imported by `assoc_static!()` next to each association, the expansion of a `for<>`
+/// declaration shadows this with the parameter and argument of the instantiation.
+#[doc(hidden)]
+pub mod instance {
+    #[allow(missing_docs)]
+    pub const __ASSOC_STATIC_INSTANCE: &[&str] = &[];
+}
+
+const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const PRIME: u64 = 0x0100_0000_01b3;
+
+const fn hash_parts(mut hash: u64, parts: &[&str], skip_whitespace: bool) -> u64 {
+    let mut part = 0;
+    while part < parts.len() {
+        let bytes = parts[part].as_bytes();
+        let mut byte = 0;
+        while byte < bytes.len() {
+            if !(skip_whitespace && bytes[byte].is_ascii_whitespace()) {
+                hash = (hash ^ bytes[byte] as u64).wrapping_mul(PRIME);
+            }
+            byte += 1;
+        }
+        hash = (hash ^ 0xff).wrapping_mul(PRIME);
+        part += 1;
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{association_id, stable_id};
+
+    #[test]
+    fn fnv1a() {
+        // reference value of FNV-1a 64 for "a" followed by the separator
+        let a = (0xcbf2_9ce4_8422_2325u64 ^ b'a' as u64).wrapping_mul(0x0100_0000_01b3);
+        assert_eq!(stable_id(&["a"]), (a ^ 0xff).wrapping_mul(0x0100_0000_01b3));
+        assert_ne!(stable_id(&["ab", "c"]), stable_id(&["a", "bc"]));
+    }
+
+    #[test]
+    fn association_id_ignores_whitespace() {
+        let spaced = association_id(["m::Wrapper < P >", "()", "u32"], &["P", "u8"]);
+        assert_eq!(
+            spaced,
+            stable_id(&["m::Wrapper<P>", "()", "u32", "P", "u8"])
+        );
+    }
+}
@@ -0,0 +1,56 @@
+use crate::AssocStatic;
+
+crate::tags! {
+    /// Tag for the domain separation string of a type.
+    pub DomainTag: &'static str;
+}
+
+/// Feeds 'data' prefixed with the domain separation string associated with the type of
+/// 'value' into 'update', the update function of any hasher. The domain is prefixed by its
+/// length as little endian `u64`, so no domain can be a prefix of another.
+///
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Signature;
+/// struct Commitment;
+/// assoc_static!(DomainTag:Signature, &'static str = "example.org/signature/v1");
+/// assoc_static!(DomainTag:Commitment, &'static str = "example.org/commitment/v1");
+///
+/// let mut signed = Vec::new();
+/// hash_domain_separated(&Signature, b"data", |bytes| signed.extend_from_slice(bytes));
+/// let mut committed = Vec::new();
+/// hash_domain_separated(&Commitment, b"data", |bytes| committed.extend_from_slice(bytes));
+/// assert_ne!(signed, committed);
+/// assert!(signed.ends_with(b"example.org/signature/v1data"));
+/// ```
+pub fn hash_domain_separated<T>(_value: &T, data: &[u8], mut update: impl FnMut(&[u8]))
+where
+    T: AssocStatic<&'static str, DomainTag> + ?Sized,
+{
+    let domain = T::get_static().as_bytes();
+    update(&(domain.len() as u64).to_le_bytes());
+    update(domain);
+    update(data);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::hash_domain_separated;
+    use crate::{assoc_static, DomainTag};
+
+    struct Short;
+    struct Long;
+    assoc_static!(DomainTag:Short, &'static str = "a");
+    assoc_static!(DomainTag:Long, &'static str = "ab");
+
+    #[test]
+    fn no_prefix_collision() {
+        let mut short = Vec::new();
+        hash_domain_separated(&Short, b"bc", |bytes| short.extend_from_slice(bytes));
+        let mut long = Vec::new();
+        hash_domain_separated(&Long, b"c", |bytes| long.extend_from_slice(bytes));
+        assert_ne!(short, long);
+        assert_eq!(&short[..8], &1u64.to_le_bytes());
+    }
+}
@@ -0,0 +1,156 @@
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::{AssocStatic, Descriptor};
+
+/// A feature flag that can be toggled at runtime. Flags are associated with the subsystem
+/// types they govern, the tag names the flag.
+#[derive(Debug)]
+pub struct Flag {
+    enabled: AtomicBool,
+}
+
+impl Flag {
+    /// Creates a flag that is initially 'enabled'.
+    pub const fn new(enabled: bool) -> Self {
+        Flag {
+            enabled: AtomicBool::new(enabled),
+        }
+    }
+
+    /// Returns true when the flag is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Enables or disables the flag.
+    pub fn set(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+}
+
+/// Returns true when the flag 'F' of 'T' is enabled.
+///
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Checkout;
+/// struct NewPayment;
+/// assoc_static!(NewPayment:Checkout, flags::Flag = flags::Flag::new(false));
+///
+/// assert!(!flags::is_enabled::<Checkout, NewPayment>());
+/// flags::set::<Checkout, NewPayment>(true);
+/// assert!(flags::is_enabled::<Checkout, NewPayment>());
+/// ```
+pub fn is_enabled<T, F>() -> bool
+where
+    T: AssocStatic<Flag, F> + ?Sized + 'static,
+    F: 'static,
+{
+    T::get_static().is_enabled()
+}
+
+/// Enables or disables the flag 'F' of 'T'.
+pub fn set<T, F>(enabled: bool)
+where
+    T: AssocStatic<Flag, F> + ?Sized + 'static,
+    F: 'static,
+{
+    T::get_static().set(enabled);
+}
+
+/// A flag listed in a `FlagSet`.
+#[derive(Clone, Copy)]
+pub struct Entry {
+    /// Returns the descriptor of the flag's association
+    pub descriptor: fn() -> Descriptor,
+    /// Returns the flag
+    pub get: fn() -> &'static Flag,
+}
+
+impl fmt::Debug for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Entry")
+            .field("descriptor", &(self.descriptor)())
+            .finish()
+    }
+}
+
+/// The flags of an application, declared with `assoc_flags!()` listing them as 'F:T'. Flags
+/// are listed whether they were ever queried or not, for admin endpoints showing and
+/// toggling all of them.
+///
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Checkout;
+/// struct NewPayment;
+/// struct OneClick;
+/// assoc_static!(NewPayment:Checkout, flags::Flag = flags::Flag::new(false));
+/// assoc_static!(OneClick:Checkout, flags::Flag = flags::Flag::new(true));
+///
+/// assoc_flags!(static FLAGS = [NewPayment:Checkout, OneClick:Checkout]);
+///
+/// for (descriptor, enabled) in FLAGS.list() {
+///     println!("{} of {}: {enabled}", descriptor.tag_name, descriptor.type_name);
+/// }
+/// assert_eq!(FLAGS.list().len(), 2);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct FlagSet(pub &'static [Entry]);
+
+impl FlagSet {
+    /// Returns all flags of the set with their current state.
+    pub fn list(&self) -> Vec<(Descriptor, bool)> {
+        self.0
+            .iter()
+            .map(|entry| ((entry.descriptor)(), (entry.get)().is_enabled()))
+            .collect()
+    }
+}
+
+/// Declares a static `FlagSet` listing flags as 'F:T'.
+#[macro_export]
+macro_rules! assoc_flags {
+    ($(#[$meta:meta])* $vis:vis static $NAME:ident = [$($F:ty:$T:ty),* $(,)?]) => {
+        $(#[$meta])*
+        $vis static $NAME: $crate::flags::FlagSet = $crate::flags::FlagSet(&[
+            $($crate::flags::Entry {
+                descriptor: <$T as $crate::AssocStatic<$crate::flags::Flag, $F>>::descriptor,
+                get: <$T as $crate::AssocStatic<$crate::flags::Flag, $F>>::get_static,
+            }),*
+        ]);
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Flag;
+    use crate::{assoc_static, flags, AssocStatic};
+
+    struct TestType;
+    struct Enabled;
+    struct Disabled;
+    assoc_static!(Enabled:TestType, Flag = Flag::new(true));
+    assoc_static!(Disabled:TestType, Flag = Flag::new(false));
+
+    assoc_flags!(static FLAGS = [Enabled:TestType, Disabled:TestType]);
+
+    #[test]
+    fn list() {
+        // listed without being queried before
+        assert_eq!(
+            FLAGS.list(),
+            [
+                (<TestType as AssocStatic<Flag, Enabled>>::descriptor(), true),
+                (
+                    <TestType as AssocStatic<Flag, Disabled>>::descriptor(),
+                    false
+                ),
+            ]
+        );
+        flags::set::<TestType, Enabled>(false);
+        assert!(!flags::is_enabled::<TestType, Enabled>());
+        assert!(!FLAGS.list()[0].1);
+    }
+}
@@ -0,0 +1,131 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::Descriptor;
+
+/// Sums up the memory used by associated static objects, overall, per crate and per tag.
+/// Only the `size_of` the static objects is accounted, heap allocations they own are not.
+///
+/// The crate of an association is taken from the path of the type the static object is
+/// associated to, types without a crate path (primitives, tuples, references) are accounted
+/// as `<builtin>`.
+///
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Example;
+/// struct Hello;
+/// assoc_static!(Example, [u32, u64] = (1, 2));
+/// assoc_static!(Hello:Example, [u8; 100] = [0; 100]);
+///
+/// let footprint: Footprint = [
+///     <Example as AssocStatic<u32>>::descriptor(),
+///     <Example as AssocStatic<u64>>::descriptor(),
+///     <Example as AssocStatic<[u8; 100], Hello>>::descriptor(),
+/// ]
+/// .into_iter()
+/// .collect();
+///
+/// assert_eq!(footprint.total(), 112);
+/// assert_eq!(footprint.per_tag()["()"], 12);
+/// println!("{footprint}");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Footprint {
+    count: usize,
+    total: usize,
+    per_crate: BTreeMap<&'static str, usize>,
+    per_tag: BTreeMap<&'static str, usize>,
+}
+
+impl Footprint {
+    /// Creates an empty footprint.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accounts the association described by 'descriptor'.
+    pub fn add(&mut self, descriptor: &Descriptor) {
+        self.count += 1;
+        self.total += descriptor.size;
+        *self
+            .per_crate
+            .entry(crate_of(descriptor.type_name))
+            .or_default() += descriptor.size;
+        *self.per_tag.entry(descriptor.tag_name).or_default() += descriptor.size;
+    }
+
+    /// Returns the number of accounted associations.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the overall size in bytes.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Returns the size in bytes per crate.
+    pub fn per_crate(&self) -> &BTreeMap<&'static str, usize> {
+        &self.per_crate
+    }
+
+    /// Returns the size in bytes per tag.
+    pub fn per_tag(&self) -> &BTreeMap<&'static str, usize> {
+        &self.per_tag
+    }
+}
+
+fn crate_of(type_name: &'static str) -> &'static str {
+    match type_name.split_once("::") {
+        Some((name, _)) if name.chars().all(|c| c.is_alphanumeric() || c == '_') => name,
+        _ => "<builtin>",
+    }
+}
+
+impl Extend<Descriptor> for Footprint {
+    fn extend<I: IntoIterator<Item = Descriptor>>(&mut self, iter: I) {
+        iter.into_iter()
+            .for_each(|descriptor| self.add(&descriptor));
+    }
+}
+
+impl FromIterator<Descriptor> for Footprint {
+    fn from_iter<I: IntoIterator<Item = Descriptor>>(iter: I) -> Self {
+        let mut footprint = Footprint::new();
+        footprint.extend(iter);
+        footprint
+    }
+}
+
+impl fmt::Display for Footprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} bytes in {} associated statics",
+            self.total, self.count
+        )?;
+        writeln!(f, "per crate:")?;
+        for (name, size) in &self.per_crate {
+            writeln!(f, "  {name}: {size} bytes")?;
+        }
+        writeln!(f, "per tag:")?;
+        for (name, size) in &self.per_tag {
+            writeln!(f, "  {name}: {size} bytes")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::crate_of;
+
+    #[test]
+    fn crate_names() {
+        assert_eq!(crate_of("assoc_static::tests::TestType"), "assoc_static");
+        assert_eq!(crate_of("core::option::Option<u32>"), "core");
+        assert_eq!(crate_of("u32"), "<builtin>");
+        assert_eq!(crate_of("&foo::Bar"), "<builtin>");
+    }
+}
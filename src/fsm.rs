@@ -0,0 +1,86 @@
+use crate::AssocStatic;
+
+crate::tags! {
+    /// Tag for the transition table of a state type, see `assoc_transitions!()`.
+    pub TransitionsTag;
+}
+
+/// A transition table, each entry is '(from, event, to)'.
+pub type Transitions<S, E> = &'static [(S, E, S)];
+
+/// Returns the state following 'state' on 'event' as defined by the transition table
+/// associated with the state type, `None` when the table has no such transition.
+///
+/// ```
+/// use crate::assoc_static::*;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// enum Door {
+///     Open,
+///     Closed,
+///     Locked,
+/// }
+///
+/// #[derive(PartialEq)]
+/// enum Action {
+///     Open,
+///     Close,
+///     Lock,
+///     Unlock,
+/// }
+///
+/// assoc_transitions!(Door, Action {
+///     Door::Open, Action::Close => Door::Closed;
+///     Door::Closed, Action::Open => Door::Open;
+///     Door::Closed, Action::Lock => Door::Locked;
+///     Door::Locked, Action::Unlock => Door::Closed;
+/// });
+///
+/// assert_eq!(step(&Door::Open, &Action::Close), Some(Door::Closed));
+/// assert_eq!(step(&Door::Locked, &Action::Open), None);
+/// ```
+pub fn step<S, E>(state: &S, event: &E) -> Option<S>
+where
+    S: AssocStatic<Transitions<S, E>, TransitionsTag> + PartialEq + Clone + 'static,
+    E: PartialEq + 'static,
+{
+    S::get_static()
+        .iter()
+        .find(|(from, on, _)| from == state && on == event)
+        .map(|(_, _, to)| to.clone())
+}
+
+/// Associates a transition table with a state type, used by `step()`.
+#[macro_export]
+macro_rules! assoc_transitions {
+    ($S:ty, $E:ty { $($FROM:expr, $EVENT:expr => $TO:expr);* $(;)? }) => {
+        $crate::assoc_static!(
+            $crate::TransitionsTag:$S,
+            $crate::Transitions<$S, $E> = &[$(($FROM, $EVENT, $TO)),*]
+        );
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::step;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Level(u8);
+
+    assoc_transitions!(Level, char {
+        Level(0), '+' => Level(1);
+        Level(1), '+' => Level(2);
+        Level(1), '-' => Level(0);
+        Level(2), '-' => Level(1);
+    });
+
+    #[test]
+    fn walk() {
+        let state = "++-+"
+            .chars()
+            .try_fold(Level(0), |state, event| step(&state, &event));
+        assert_eq!(state, Some(Level(2)));
+        assert_eq!(step(&Level(2), &'+'), None);
+    }
+}
@@ -0,0 +1,145 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::sync::atomic::{self, AtomicU64};
+
+use crate::AssocStatic;
+
+crate::tags! {
+    /// Tag for the `IdCounter` of a type, associated with `assoc_ids!()`.
+    pub IdTag;
+}
+
+/// The counter handing out the `Id`s of a type.
+#[derive(Debug, Default)]
+pub struct IdCounter(AtomicU64);
+
+impl IdCounter {
+    /// Creates a counter starting at zero.
+    pub const fn new() -> Self {
+        IdCounter(AtomicU64::new(0))
+    }
+
+    /// Returns the number of ids handed out so far.
+    pub fn high_water(&self) -> u64 {
+        self.0.load(atomic::Ordering::Relaxed)
+    }
+
+    fn next(&self) -> u64 {
+        self.0.fetch_add(1, atomic::Ordering::Relaxed)
+    }
+}
+
+/// An id that is unique among the ids of 'T'. Ids of different types are distinct types, so
+/// they can not be mixed up.
+///
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct User;
+/// struct Order;
+/// assoc_ids!(User, Order);
+///
+/// let first = Id::<User>::next();
+/// let second = Id::<User>::next();
+/// assert_ne!(first, second);
+/// assert_eq!(Id::<Order>::next().value(), 0);
+/// assert_eq!(Id::<User>::high_water(), 2);
+/// ```
+pub struct Id<T: ?Sized>(u64, PhantomData<fn() -> T>);
+
+impl<T: ?Sized> Id<T> {
+    /// Creates an id from its raw value, for ids that were stored or sent elsewhere.
+    pub const fn from_raw(value: u64) -> Self {
+        Id(value, PhantomData)
+    }
+
+    /// Returns the raw value of the id.
+    pub const fn value(self) -> u64 {
+        self.0
+    }
+}
+
+impl<T: AssocStatic<IdCounter, IdTag> + ?Sized> Id<T> {
+    /// Returns a new id of 'T'.
+    pub fn next() -> Self {
+        Id::from_raw(T::get_static().next())
+    }
+
+    /// Returns the number of ids of 'T' handed out so far.
+    pub fn high_water() -> u64 {
+        T::get_static().high_water()
+    }
+}
+
+impl<T: ?Sized> Clone for Id<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: ?Sized> Copy for Id<T> {}
+
+impl<T: ?Sized> PartialEq for Id<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T: ?Sized> Eq for Id<T> {}
+
+impl<T: ?Sized> PartialOrd for Id<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: ?Sized> Ord for Id<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl<T: ?Sized> Hash for Id<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for Id<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Id<{}>({})", std::any::type_name::<T>(), self.0)
+    }
+}
+
+impl<T: ?Sized> fmt::Display for Id<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Associates an `IdCounter` with each of the listed types, enabling `Id::<T>::next()`.
+#[macro_export]
+macro_rules! assoc_ids {
+    ($($T:ty),+ $(,)?) => {
+        $($crate::assoc_static!($crate::IdTag:$T, $crate::IdCounter = $crate::IdCounter::new());)+
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Id;
+
+    struct TestType;
+    assoc_ids!(TestType, str);
+
+    #[test]
+    fn sequence() {
+        let ids: Vec<_> = (0..3).map(|_| Id::<TestType>::next().value()).collect();
+        assert_eq!(ids, [0, 1, 2]);
+        assert_eq!(Id::<TestType>::high_water(), 3);
+        assert_eq!(Id::<str>::next(), Id::from_raw(0));
+        assert!(format!("{:?}", Id::<str>::from_raw(7)).ends_with("<str>(7)"));
+    }
+}
@@ -0,0 +1,75 @@
+use std::any::{type_name, TypeId};
+use std::cell::RefCell;
+
+struct Initializing {
+    id: TypeId,
+    name: String,
+}
+
+thread_local! {
+    static INITIALIZING: RefCell<Vec<Initializing>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Only a helper, needs to be public because of the macro.
+/// Tracks the lazy associations being initialized on the current thread and panics when an
+/// initializer (directly or indirectly) reads its own association, which would otherwise
+/// deadlock. Cycles spanning multiple threads are not detected.
+#[doc(hidden)]
+pub struct InitGuard;
+
+impl InitGuard {
+    /// Marks the association of 'TARGET' with 'TAG' to 'T' as being initialized.
+    #[track_caller]
+    pub fn new<T: 'static + ?Sized, TARGET: 'static, TAG: 'static>() -> Self {
+        let id = TypeId::of::<(TARGET, TAG, std::marker::PhantomData<T>)>();
+        let name = format!(
+            "{} of {} with tag {}",
+            type_name::<TARGET>(),
+            type_name::<T>(),
+            type_name::<TAG>()
+        );
+        INITIALIZING.with(|initializing| {
+            let mut initializing = initializing.borrow_mut();
+            if let Some(pos) = initializing.iter().position(|entry| entry.id == id) {
+                let chain: Vec<_> = initializing[pos..]
+                    .iter()
+                    .map(|entry| entry.name.as_str())
+                    .chain(std::iter::once(name.as_str()))
+                    .collect();
+                let chain = chain.join(" -> ");
+                // don't leave the stack locked for the unwinding guards
+                drop(initializing);
+                panic!("recursive initialization of associated static: {chain}");
+            }
+            initializing.push(Initializing { id, name });
+        });
+        InitGuard
+    }
+}
+
+impl Drop for InitGuard {
+    fn drop(&mut self) {
+        INITIALIZING.with(|initializing| initializing.borrow_mut().pop());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{assoc_static, AssocStatic};
+
+    struct TestType;
+    struct First;
+    struct Second;
+    assoc_static!(First:TestType, u32 = {
+        *<TestType as AssocStatic<u32, Second>>::get_static()
+    }; boxed);
+    assoc_static!(Second:TestType, u32 = {
+        *<TestType as AssocStatic<u32, First>>::get_static()
+    }; boxed);
+
+    #[test]
+    #[should_panic(expected = "recursive initialization of associated static: u32 of")]
+    fn cycle() {
+        <TestType as AssocStatic<u32, First>>::get_static();
+    }
+}
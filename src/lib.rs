@@ -2,18 +2,323 @@
 #![warn(missing_docs)]
 #![warn(rustdoc::missing_crate_level_docs)]
 
+#[cfg(feature = "arena")]
+mod arena;
+#[cfg(feature = "arena")]
+pub use arena::{Arena, ArenaTag};
+
+mod assoc_map;
+pub use assoc_map::{AssocMap, StaticMap};
+
+mod assoc_ref;
+pub use assoc_ref::AssocRef;
+
+mod cache;
+pub use cache::{Cache, CacheTag};
+
+mod channel;
+pub use channel::{Channel, ChannelTag};
+
+mod combinators;
+pub use combinators::{map_assoc, with_assoc, zip_assoc};
+
+mod context;
+pub use context::{ContextAssoc, ErrorContext, ErrorContextTag, WithContext};
+
+mod delegate;
+
+mod descriptor;
+#[doc(hidden)]
+pub use descriptor::{association_id, instance};
+pub use descriptor::{stable_id, Descriptor, Location};
+
+mod domain;
+pub use domain::{hash_domain_separated, DomainTag};
+
+/// Feature flags associated with types.
+pub mod flags;
+
+mod footprint;
+pub use footprint::Footprint;
+
+#[cfg(feature = "fsm")]
+mod fsm;
+#[cfg(feature = "fsm")]
+pub use fsm::{step, Transitions, TransitionsTag};
+
+mod id;
+pub use id::{Id, IdCounter, IdTag};
+
+mod init_guard;
+#[doc(hidden)]
+pub use init_guard::InitGuard;
+
+mod lock;
+pub use lock::{AssocMutex, AssocRwLock};
+
+mod memo;
+pub use memo::{Memo, MemoTag};
+
+mod message;
+pub use message::{decode_header, encode_header, HeaderError, MessageInfo, MessageTag, HEADER_LEN};
+
+#[cfg(feature = "model")]
+mod model;
+#[cfg(feature = "model")]
+pub use model::{ModelInfo, ModelTag};
+
+mod obfuscate;
+#[doc(hidden)]
+pub use obfuscate::{deobfuscate, obfuscate};
+
+mod percore;
+pub use percore::PerCore;
+
+#[cfg(feature = "peripheral")]
+mod peripheral;
+#[cfg(feature = "peripheral")]
+pub use peripheral::{regs, Peripheral, PeripheralTag};
+
+#[cfg(feature = "pool")]
+mod pool;
+#[cfg(feature = "pool")]
+pub use pool::{Executor, Job, Pool, PoolTag};
+
+mod racy;
+#[doc(hidden)]
+pub use racy::RacyCell;
+
+#[cfg(feature = "ratelimit")]
+mod ratelimit;
+#[cfg(feature = "ratelimit")]
+pub use ratelimit::{RateLimitTag, RateLimiter};
+
+/// Associations resolvable by name.
+pub mod registry;
+pub use registry::Registry;
+
+mod sampling;
+pub use sampling::{Sampler, SamplingTag, ShouldSample};
+
+mod seqlock;
+pub use seqlock::{Pod, SeqLock};
+
+mod snapshot;
+pub use snapshot::{Restorable, Snapshot, Snapshots};
+
+/// Access statistics, enabled with the 'stats' feature.
+#[cfg(feature = "stats")]
+pub mod stats;
+
+#[cfg(feature = "tables")]
+mod tables;
+#[cfg(feature = "tables")]
+pub use tables::{keyword, precedence, KeywordsTag, PrecedenceTag, Table};
+
+mod tags;
+pub use tags::{DefaultsTag, HasTags, Tag};
+
+mod take;
+pub use take::AssocTake;
+#[doc(hidden)]
+pub use take::TakeCell;
+
+#[cfg(feature = "validate")]
+mod validated;
+#[cfg(feature = "validate")]
+pub use validated::{Rule, Rules, RulesTag, Subject, Validated, ValidationError};
+
+mod watched;
+pub use watched::{on_change, Watched};
+
+#[cfg(feature = "wavetable")]
+mod wavetable;
+#[cfg(feature = "wavetable")]
+pub use wavetable::{SampleFormat, Wavetable, WavetableTag};
+
+mod weak;
+pub use weak::{WeakSlot, WeakSlotTag};
+
+/// Derives `assoc_delegate!()` for a newtype, forwarding the associations listed in
+/// `#[assoc_delegate(...)]` attributes from the inner type.
+///
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Inner;
+/// struct Hello;
+/// assoc_static!(Inner, u32 = 42);
+/// assoc_static!(Hello:Inner, &'static str = "Hello World!");
+///
+/// #[derive(AssocDelegate)]
+/// #[assoc_delegate(u32, Hello: &'static str)]
+/// struct Wrapper(Inner);
+///
+/// assert_eq!(<Wrapper as AssocStatic<u32>>::get_static(), &42);
+/// assert_eq!(AssocStatic::<_, Hello>::assoc(&Wrapper(Inner)), &"Hello World!");
+/// ```
+#[cfg(feature = "derive")]
+pub use assoc_static_derive::AssocDelegate;
+
+/// Derives a lazily created default instance of the type, associated under the `DefaultsTag`.
+///
+/// ```
+/// use crate::assoc_static::*;
+///
+/// #[derive(Default, AssocDefault)]
+/// struct Config {
+///     verbose: bool,
+/// }
+///
+/// assert!(!<Config as AssocStatic<Config, DefaultsTag>>::get_static().verbose);
+/// ```
+#[cfg(feature = "derive")]
+pub use assoc_static_derive::AssocDefault;
+
+/// Derives the `MessageInfo` of a wire message type from an `#[assoc_message(...)]`
+/// attribute.
+///
+/// ```
+/// use crate::assoc_static::*;
+///
+/// #[derive(AssocMessage)]
+/// #[assoc_message(magic = 0xcafe, id = 7, max_len = 64)]
+/// struct Status;
+///
+/// assert_eq!(MessageTag::of::<Status>().id, 7);
+/// assert_eq!(MessageTag::of::<Status>().min_len, 0);
+/// let header = encode_header::<Status>(10).unwrap();
+/// assert_eq!(decode_header::<Status>(&header), Ok(10));
+/// ```
+#[cfg(feature = "derive")]
+pub use assoc_static_derive::AssocMessage;
+
+/// Runs a test isolated by a `Snapshots` set. The test sees the objects of the set in the
+/// state they had before the first isolated test, its writes are rolled back afterwards.
+///
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Config;
+/// assoc_static!(Config, u32 = 8080; sync(mutex));
+/// assoc_snapshots!(static STATE = [():Config => std::sync::Mutex<u32>]);
+///
+/// #[assoc_test(STATE)]
+/// fn change_port() {
+///     *<Config as AssocMutex<u32>>::lock_static() = 8443;
+/// }
+///
+/// #[assoc_test(STATE)]
+/// fn default_port() {
+///     assert_eq!(*<Config as AssocMutex<u32>>::lock_static(), 8080);
+/// }
+/// ```
+#[cfg(feature = "derive")]
+pub use assoc_static_derive::assoc_test;
+
 /// Associates a static object of type T and a marker TAG.
 /// Use the `assoc_static!()` macro for implemeting this trait on types.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` has no associated static object of type `{T}` with tag `{TAG}`",
+    note = "associations are declared with `assoc_static!({TAG}:{Self}, {T} = ...)`"
+)]
 pub trait AssocStatic<T, TAG = ()> {
     /// Returns a reference to the associated static object of the Self type
     fn get_static() -> &'static T;
 
     /// Returns a reference to the associated object from an instance.
+    fn assoc(&self) -> &'static T {
+        Self::get_static()
+    }
+
+    /// Returns a reference to the associated object from an instance.
+    #[deprecated(note = "use `assoc()`, `from` is easily confused with `From::from`")]
     fn from(_this: &Self) -> &'static T {
         Self::get_static()
     }
+
+    /// Returns a description of this association.
+    ///
+    /// ```
+    /// use crate::assoc_static::*;
+    ///
+    /// struct Example;
+    /// assoc_static!(Example, u32 = 42);
+    ///
+    /// let descriptor = <Example as AssocStatic<u32>>::descriptor();
+    /// assert!(descriptor.type_name.ends_with("Example"));
+    /// assert_eq!(descriptor.target_name, "u32");
+    /// assert_eq!(descriptor.size, 4);
+    /// assert!(descriptor.location.is_some());
+    /// ```
+    fn descriptor() -> Descriptor
+    where
+        Self: 'static,
+        T: 'static,
+        TAG: 'static,
+    {
+        Descriptor::new::<Self, T, TAG>(None)
+    }
+
+    /// Returns the id of this association, a hash of the type, tag and target, see
+    /// `stable_id()`.
+    fn id() -> u64
+    where
+        Self: 'static,
+        T: 'static,
+        TAG: 'static,
+    {
+        Self::descriptor().id
+    }
+}
+
+/// Gives const access to an associated static object. Implemented by `assoc_static!()` for
+/// all associations except the lazily initialized ones.
+///
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Example;
+/// assoc_static!(Example, u32 = 42);
+///
+/// const ANSWER: &u32 = <Example as AssocStaticRef<u32>>::STATIC_REF;
+/// assert_eq!(ANSWER, &42);
+/// assert!(std::ptr::eq(ANSWER, Example::get_static()));
+/// ```
+pub trait AssocStaticRef<T: 'static, TAG = ()>: AssocStatic<T, TAG> {
+    /// A reference to the associated static object, usable in const contexts.
+    const STATIC_REF: &'static T;
+}
+
+/// Shorthand for `AssocStatic<T, ()>`, implemented for every untagged association.
+/// Generic bounds read `V: AssocStaticUntagged<Config>` and calls never need the `()` tag
+/// spelled out.
+///
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Config {
+///     retries: u32,
+/// }
+///
+/// struct Example;
+/// assoc_static!(Example, Config = Config { retries: 3 });
+///
+/// fn retries<V: AssocStaticUntagged<Config>>() -> u32 {
+///     V::get_untagged().retries
+/// }
+///
+/// assert_eq!(retries::<Example>(), 3);
+/// ```
+pub trait AssocStaticUntagged<T>: AssocStatic<T> {
+    /// Returns a reference to the untagged associated static object of the Self type.
+    fn get_untagged() -> &'static T {
+        Self::get_static()
+    }
 }
 
+impl<T, V: AssocStatic<T> + ?Sized> AssocStaticUntagged<T> for V {}
+
 /// Helper macro doing the boilerplate implementation.
 /// This must be a macro because statics can not take template parameters from the outer scope.
 ///
@@ -35,7 +340,7 @@ pub trait AssocStatic<T, TAG = ()> {
 ///
 /// // get it from an object
 /// let example = Example;
-/// assert_eq!(AssocStatic::from(&example), &"&str associated to Example");
+/// assert_eq!(AssocStatic::assoc(&example), &"&str associated to Example");
 /// ```
 ///
 /// The 'TAG' is required when one needs to disambiguate between different target values of
@@ -44,6 +349,9 @@ pub trait AssocStatic<T, TAG = ()> {
 /// preferably you just make a zero-size struct just for this purpose. It is only used as
 /// marker for disambiguation.
 ///
+/// Declaring the same association (type, target and tag) twice is rejected by the compiler
+/// with a 'conflicting implementations' error (E0119) that points at both invocations.
+///
 /// Disambiguate between different static objects:
 /// ```
 /// use crate::assoc_static::*;
@@ -61,11 +369,29 @@ pub trait AssocStatic<T, TAG = ()> {
 /// let example = Example;
 ///
 /// // resolve the ambiguity with a turbofish
-/// assert_eq!(AssocStatic::<_, Hello>::from(&example), &"Hello World!");
-/// assert_eq!(AssocStatic::<_, ExplainType>::from(&example), &"This is 'struct Example'");
+/// assert_eq!(AssocStatic::<_, Hello>::assoc(&example), &"Hello World!");
+/// assert_eq!(AssocStatic::<_, ExplainType>::assoc(&example), &"This is 'struct Example'");
+/// ```
+///
+/// Tuples of tags disambiguate along independent axes without defining a marker type for
+/// each combination:
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Example;
+/// struct English;
+/// struct German;
+/// struct Web;
+/// struct Email;
+///
+/// assoc_static!((English, Web):Example, &'static str = "Welcome!");
+/// assoc_static!((German, Email):Example, &'static str = "Sehr geehrte Damen und Herren,");
+///
+/// assert_eq!(AssocStatic::<_, (English, Web)>::assoc(&Example), &"Welcome!");
 /// ```
 ///
-/// Make an association between foreign types:
+/// Make an association between foreign types, this works for unsized types like `str`,
+/// `[u8]` or `Path` as well:
 /// ```
 /// use crate::assoc_static::*;
 ///
@@ -74,44 +400,868 @@ pub trait AssocStatic<T, TAG = ()> {
 /// assoc_static!(I32ExampleStr:i32, &'static str = "&str associated to i32");
 ///
 /// // get it
-/// assert_eq!(AssocStatic::from(&100i32), &"&str associated to i32");
+/// assert_eq!(AssocStatic::assoc(&100i32), &"&str associated to i32");
+///
+/// // attach a '&str' to str
+/// assoc_static!(I32ExampleStr:str, &'static str = "&str associated to str");
+/// assert_eq!(AssocStatic::assoc("hello"), &"&str associated to str");
+/// ```
+///
+/// Static objects can be associated to trait objects as well, this attaches metadata to the
+/// trait itself rather than to each implementor:
+/// ```
+/// use crate::assoc_static::*;
+///
+/// trait Shape {}
+/// assoc_static!(dyn Shape, &'static str = "something with an area");
+///
+/// assert_eq!(
+///     <dyn Shape as AssocStatic<&str>>::get_static(),
+///     &"something with an area"
+/// );
+/// ```
+///
+/// Appending '; also_default' to a tagged association makes the same static object
+/// reachable with the default '()' tag as well:
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Example;
+/// struct Hello;
+/// assoc_static!(Hello:Example, &'static str = "Hello World!"; also_default);
+///
+/// let example = Example;
+/// assert_eq!(AssocStatic::<_, Hello>::assoc(&example), &"Hello World!");
+/// assert_eq!(AssocStatic::<_, ()>::assoc(&example), &"Hello World!");
+/// ```
+///
+/// Appending '; boxed' places the static object on the heap. 'INIT' is then evaluated at
+/// runtime on first access instead of at compile time, which keeps large tables out of the
+/// binary's data sections and can call non-const functions. Boxed associations do not
+/// implement `AssocStaticRef`:
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Example;
+/// assoc_static!(Example, Vec<u64> = (0..4096).map(|n| n * n).collect(); boxed);
+///
+/// assert_eq!(<Example as AssocStatic<Vec<u64>>>::get_static()[3], 9);
+/// ```
+///
+/// Boxed associations can be checked on first access by appending '; validate = VALIDATOR'
+/// or '; debug_validate = VALIDATOR', the latter only checks when debug assertions are
+/// enabled. The validator returns a `Result<(), E: Display>` and the first access panics
+/// with the error and the names of the association when it fails:
+/// ```should_panic
+/// use crate::assoc_static::*;
+///
+/// struct Port;
+/// struct Example;
+/// assoc_static!(Port:Example, u16 = 0; boxed; validate = |port: &u16| match port {
+///     0 => Err("port must not be zero"),
+///     _ => Ok(()),
+/// });
+///
+/// // panics with "invalid associated static 'u16' of ... : port must not be zero"
+/// <Example as AssocStatic<u16, Port>>::get_static();
+/// ```
+///
+/// Initializers can refer to other associated statics. Plain initializers are evaluated at
+/// compile time and read other plain associations through `static_ref!()`, a cyclic
+/// dependency is a compile error. Boxed initializers run on first access and can read any
+/// association with `get_static()`, initializing it first when needed:
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Example;
+/// struct Base;
+/// struct Sum;
+/// struct Squares;
+/// assoc_static!(Base:Example, [u32; 4] = [1, 2, 3, 4]);
+///
+/// // computed at compile time
+/// assoc_static!(Sum:Example, u32 = {
+///     let base = static_ref!(Base:Example, [u32; 4]);
+///     base[0] + base[1] + base[2] + base[3]
+/// });
+///
+/// // computed on first access
+/// assoc_static!(Squares:Example, Vec<u32> = {
+///     let base = <Example as AssocStatic<[u32; 4], Base>>::get_static();
+///     base.iter().map(|n| n * n).collect()
+/// }; boxed);
+///
+/// assert_eq!(<Example as AssocStatic<u32, Sum>>::get_static(), &10);
+/// assert_eq!(<Example as AssocStatic<Vec<u32>, Squares>>::get_static(), &[1, 4, 9, 16]);
+/// ```
+///
+/// Initializers can refer to the type as `Self`, except when one static object is shared by
+/// several types with 'TAG:A | B':
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Example([u8; 3]);
+/// struct Size;
+/// assoc_static!(Size:Example, usize = std::mem::size_of::<Self>());
+///
+/// assert_eq!(<Example as AssocStatic<usize, Size>>::get_static(), &3);
+/// ```
+///
+/// Generic types can be associated for a list of known instantiations with a 'for<P in
+/// [...]>' prefix. The declaration is repeated for each listed type with 'P' being an alias for
+/// it:
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Wrapper<T>(T);
+/// assoc_static!(for<T in [u8, u16, u32]> Wrapper<T>, usize = std::mem::size_of::<T>());
+///
+/// assert_eq!(<Wrapper<u16> as AssocStatic<usize>>::get_static(), &2);
+/// assert_eq!(<Wrapper<u32> as AssocStatic<usize>>::get_static(), &4);
+/// ```
+///
+/// Complex tables can be computed in a `const { ... }` block, which is always evaluated at
+/// compile time, even for boxed associations. Helper items declared inside the block stay
+/// local to the initializer:
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Crc;
+/// assoc_static!(Crc, [u8; 256] = const {
+///     const POLY: u8 = 0x07;
+///     const fn entry(mut crc: u8) -> u8 {
+///         let mut bit = 0;
+///         while bit < 8 {
+///             crc = if crc & 0x80 != 0 { (crc << 1) ^ POLY } else { crc << 1 };
+///             bit += 1;
+///         }
+///         crc
+///     }
+///     let mut table = [0; 256];
+///     let mut n = 0;
+///     while n < 256 {
+///         table[n] = entry(n as u8);
+///         n += 1;
+///     }
+///     table
+/// });
+///
+/// assert_eq!(<Crc as AssocStatic<[u8; 256]>>::get_static()[1], 0x07);
+/// ```
+///
+/// A boxed initializer that reads its own association, directly or through other boxed
+/// associations, panics with the chain of associations involved instead of deadlocking.
+///
+/// An existing static can be associated with '= static PATH', the association then refers
+/// to that static instead of defining a new one:
+/// ```
+/// use crate::assoc_static::*;
+///
+/// static BANNER: &str = "legacy banner";
+///
+/// struct Example;
+/// assoc_static!(Example, &'static str = static BANNER);
+///
+/// assert!(std::ptr::eq(<Example as AssocStatic<&str>>::get_static(), &BANNER));
+/// ```
+///
+/// Writing the target as a static item declaration defines a named static in the
+/// surrounding module and associates it, so non-generic code and debuggers can refer to it
+/// by name:
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Example;
+/// assoc_static!(Example, pub static EXAMPLE_BANNER: &'static str = "Example");
+///
+/// assert!(std::ptr::eq(<Example as AssocStatic<&str>>::get_static(), &EXAMPLE_BANNER));
+/// ```
+///
+/// Appending '; racy' is a cheaper alternative to '; boxed' that stores the boxed object
+/// behind a single atomic pointer instead of a `OnceLock`, no thread ever waits for another.
+/// It still allocates and needs std like the rest of this crate. Threads racing on the first
+/// access each evaluate 'INIT', the first value stored wins and the others are dropped.
+/// 'INIT' should therefore be free of side effects and reentrant access is not detected:
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Example;
+/// assoc_static!(Example, Vec<u32> = (1..=3).collect(); racy);
+///
+/// assert_eq!(Example::get_static(), &[1, 2, 3]);
+/// ```
+///
+/// Appending '; cold' marks the accessor of a rarely used association as `#[cold]` and
+/// `#[inline(never)]`, '; cold = unsafe "SECTION"' additionally places the static object in
+/// the given link section. This is `unsafe` because the section must suit the data, placing
+/// an object with interior mutability in a read-only section is undefined behavior. Section
+/// names are platform specific:
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Example;
+/// struct Help;
+/// assoc_static!(Help:Example, &'static str = "a long help text"; cold);
+///
+/// assert_eq!(<Example as AssocStatic<&str, Help>>::get_static(), &"a long help text");
+/// ```
+///
+/// Appending '; debug_only' associates `Option<TARGET>`, which is `Some(INIT)` when debug
+/// assertions are enabled and `None` otherwise. 'INIT' is not compiled into release builds,
+/// which keeps diagnostic tables out of shipped binaries:
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Example;
+/// struct Trace;
+/// assoc_static!(Trace:Example, &'static [&'static str] = &["init", "run"]; debug_only);
+///
+/// let trace = <Example as AssocStatic<Option<&[&str]>, Trace>>::get_static();
+/// assert_eq!(trace.is_some(), cfg!(debug_assertions));
+/// ```
+///
+/// String and byte string literals associated as `String = obfuscated "..."` or
+/// `Vec<u8> = obfuscated b"..."` are stored XOR obfuscated in the binary and decoded on first
+/// access. This only keeps them from showing up in a plain `strings` dump, it is no
+/// protection against someone reading the code:
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Example;
+/// struct Token;
+/// assoc_static!(Token:Example, String = obfuscated "not in the binary");
+///
+/// assert_eq!(<Example as AssocStatic<String, Token>>::get_static(), "not in the binary");
+/// ```
+///
+/// Appending '; sync(mutex)' wraps a 'TARGET' which is `Send` but not `Sync` in a `Mutex`,
+/// the association is then made for the wrapped type and locked with `AssocMutex`.
+/// '; sync(rwlock)' wraps it in a `RwLock` locked with `AssocRwLock` instead, this gives
+/// concurrent readers but requires 'TARGET' to be `Send + Sync`.
+///
+/// Appending '; take' declares a singleton that is taken once as `&'static mut TARGET`
+/// through the `AssocTake` trait instead of implementing `AssocStatic`.
+///
+/// When many types shall carry the exact same value, listing them separated by '|' lets
+/// them share one static object instead of each having its own copy:
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Dog;
+/// struct Cat;
+/// struct Legs;
+/// assoc_static!(Legs:Dog | Cat, u32 = 4);
+///
+/// assert!(std::ptr::eq(
+///     <Dog as AssocStatic<u32, Legs>>::get_static(),
+///     <Cat as AssocStatic<u32, Legs>>::get_static(),
+/// ));
+/// ```
+///
+/// The initializer can be selected by configuration predicates with 'cfg { PREDICATE => INIT,
+/// ..., _ => DEFAULT }'. The first matching predicate wins, the '_' default is optional.
+/// Only the selected association is compiled, so the other initializers may refer to
+/// platform specific items:
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Example;
+/// assoc_static!(Example, &'static str = cfg {
+///     windows => "windows",
+///     unix => "unix",
+///     target_family = "wasm" => "wasm",
+///     _ => "unknown",
+/// });
+///
+/// # #[cfg(unix)]
+/// assert_eq!(Example::get_static(), &"unix");
+/// ```
+///
+/// Predicates are evaluated in the crate invoking the macro, this keeps alternative
+/// configurations selected by that crate's cargo features in one place:
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Example;
+/// assoc_static!(Example, &'static [u8] = cfg {
+///     feature = "big-tables" => &[0; 4096],
+///     _ => &[0; 16],
+/// });
+///
+/// assert_eq!(Example::get_static().len(), 16);
+/// ```
+///
+/// Multiple targets can be associated in one declaration by listing them in brackets. The
+/// initializer is a tuple holding one value for each target, all of them are stored in one
+/// shared static. At most 12 targets can be listed:
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Example;
+/// assoc_static!(Example, [u32, &'static str] = (42, "the answer"));
+///
+/// assert_eq!(<Example as AssocStatic<u32>>::get_static(), &42);
+/// assert_eq!(<Example as AssocStatic<&str>>::get_static(), &"the answer");
 /// ```
 #[macro_export]
 macro_rules! assoc_static {
-    ($TAG:ty:$T:ty, $TARGET:ty = $INIT:expr) => {
-        impl $crate::AssocStatic<$TARGET, $TAG> for $T {
-            fn get_static() -> &'static $TARGET {
-                static ASSOCIATED_STATIC: (
-                    $TARGET,
-                    std::marker::PhantomData<$crate::MakeSync<$T>>,
-                    std::marker::PhantomData<$crate::MakeSync<$TAG>>,
-                ) = ($INIT, std::marker::PhantomData, std::marker::PhantomData);
-                &ASSOCIATED_STATIC.0
+    (@descriptor $TAG:ty:$T:ty, $TARGET:ty) => {
+        fn descriptor() -> $crate::Descriptor
+        where
+            Self: 'static,
+            $TARGET: 'static,
+            $TAG: 'static,
+        {
+            const ID: u64 = $crate::association_id(
+                [
+                    concat!(module_path!(), "::", stringify!($T)),
+                    stringify!($TAG),
+                    stringify!($TARGET),
+                ],
+                __ASSOC_STATIC_INSTANCE,
+            );
+            $crate::Descriptor {
+                id: ID,
+                ..$crate::Descriptor::new::<Self, $TARGET, $TAG>(Some($crate::Location {
+                    file: file!(),
+                    line: line!(),
+                    column: column!(),
+                }))
             }
         }
     };
-    ($T:ty, $TARGET:ty = $INIT:expr) => {
-        impl $crate::AssocStatic<$TARGET, ()> for $T {
+    (@instance) => {
+        // '@for' shadows this default with the instantiation of the association
+        #[allow(unused_imports)]
+        use $crate::instance::*;
+    };
+    (@self $T:ty, $TARGET:ty = $INIT:expr) => {{
+        // evaluating 'INIT' in an impl for 'T' lets it refer to 'Self', the constant is used
+        // only once to initialize a static
+        #[allow(clippy::declare_interior_mutable_const)]
+        trait __AssocStaticInit {
+            const INIT: $TARGET;
+        }
+        #[allow(clippy::declare_interior_mutable_const)]
+        impl __AssocStaticInit for $T {
+            const INIT: $TARGET = $INIT;
+        }
+        <$T as __AssocStaticInit>::INIT
+    }};
+    (@impl $(#[$ATTR:meta])* $TAG:ty:$T:ty, $TARGET:ty, $REF:expr) => {
+        impl $crate::AssocStatic<$TARGET, $TAG> for $T {
+            $(#[$ATTR])*
             fn get_static() -> &'static $TARGET {
-                static ASSOCIATED_STATIC: (
-                    $TARGET,
-                    std::marker::PhantomData<$crate::MakeSync<$T>>,
-                    std::marker::PhantomData<()>,
-                ) = ($INIT, std::marker::PhantomData, std::marker::PhantomData);
-                &ASSOCIATED_STATIC.0
+                $crate::assoc_static_hit!($TAG:$T, $TARGET);
+                $REF
             }
+            $crate::assoc_static!(@descriptor $TAG:$T, $TARGET);
+        }
+        impl $crate::AssocStaticRef<$TARGET, $TAG> for $T {
+            const STATIC_REF: &'static $TARGET = $REF;
         }
     };
-}
-
-/// Only a helper, needs to be public because of the macro
-#[doc(hidden)]
-pub struct MakeSync<T>(T);
-unsafe impl<T> Sync for MakeSync<T> {}
-
+    (@validate $TAG:ty:$T:ty, $TARGET:ty, $VALUE:expr, $VALIDATE:expr) => {
+        $crate::validate_helper::<$T, $TARGET, $TAG, _>($VALUE, $VALIDATE)
+    };
+    (@debug_validate $TAG:ty:$T:ty, $TARGET:ty, $VALUE:expr, $VALIDATE:expr) => {
+        if cfg!(debug_assertions) {
+            $crate::validate_helper::<$T, $TARGET, $TAG, _>($VALUE, $VALIDATE)
+        }
+    };
+    (@cfg [$($PREV:meta),*] $TAG:ty:$T:ty, $TARGET:ty;) => {};
+    (@cfg [$($PREV:meta),*] $TAG:ty:$T:ty, $TARGET:ty; _ => $INIT:expr $(,)?) => {
+        #[cfg(not(any($($PREV),*)))]
+        $crate::assoc_static!($TAG:$T, $TARGET = $INIT);
+    };
+    (
+        @cfg [$($PREV:meta),*] $TAG:ty:$T:ty, $TARGET:ty;
+        $CFG:meta => $INIT:expr $(, $($REST:tt)*)?
+    ) => {
+        #[cfg(all($CFG, not(any($($PREV),*))))]
+        $crate::assoc_static!($TAG:$T, $TARGET = $INIT);
+        $crate::assoc_static!(@cfg [$($PREV,)* $CFG] $TAG:$T, $TARGET; $($($REST)*)?);
+    };
+    (@multi $TAG:ty:$T:ty, $STATIC:ident, [$IDX:tt $($IDXS:tt)*] $TARGET:ty $(, $REST:ty)*) => {
+        $crate::assoc_static!(@impl $TAG:$T, $TARGET, &$STATIC.$IDX);
+        $crate::assoc_static!(@multi $TAG:$T, $STATIC, [$($IDXS)*] $($REST),*);
+    };
+    (@multi $TAG:ty:$T:ty, $STATIC:ident, [$($IDXS:tt)*]) => {};
+    (@for $P:ident [$ARG:ty $(, $ARGS:ty)*] $($REST:tt)*) => {
+        const _: () = {
+            type $P = $ARG;
+            // shadows the glob imported 'instance::__ASSOC_STATIC_INSTANCE'
+            #[allow(dead_code)]
+            const __ASSOC_STATIC_INSTANCE: &[&str] = &[stringify!($P), stringify!($ARG)];
+            $crate::assoc_static!($($REST)*);
+        };
+        $crate::assoc_static!(@for $P [$($ARGS),*] $($REST)*);
+    };
+    (@for $P:ident [] $($REST:tt)*) => {};
+    (for<$P:ident in [$($ARG:ty),+ $(,)?]> $($REST:tt)*) => {
+        $crate::assoc_static!(@for $P [$($ARG),+] $($REST)*);
+    };
+    ($TAG:ty:$T:ty, [$($TARGET:ty),+ $(,)?] = $INIT:expr) => {
+        $crate::assoc_static!(@instance);
+        const _: () = {
+            static ASSOCIATED_STATIC: ($($TARGET,)+) =
+                $crate::assoc_static!(@self $T, ($($TARGET,)+) = $INIT);
+            $crate::assoc_static!(
+                @multi $TAG:$T, ASSOCIATED_STATIC, [0 1 2 3 4 5 6 7 8 9 10 11] $($TARGET),+
+            );
+        };
+    };
+    ($T:ty, [$($TARGET:ty),+ $(,)?] = $INIT:expr) => {
+        $crate::assoc_static!(():$T, [$($TARGET),+] = $INIT);
+    };
+    ($TAG:ty:$T:ty, $TARGET:ty = cfg { $($CFG:tt)* }) => {
+        $crate::assoc_static!(@cfg [] $TAG:$T, $TARGET; $($CFG)*);
+    };
+    ($T:ty, $TARGET:ty = cfg { $($CFG:tt)* }) => {
+        $crate::assoc_static!(@cfg [] ():$T, $TARGET; $($CFG)*);
+    };
+    (
+        $TAG:ty:$T:ty,
+        $(#[$meta:meta])* $vis:vis static $NAME:ident: $TARGET:ty = $INIT:expr
+    ) => {
+        $crate::assoc_static!(@instance);
+        $(#[$meta])*
+        $vis static $NAME: $TARGET = $crate::assoc_static!(@self $T, $TARGET = $INIT);
+        $crate::assoc_static!(@impl $TAG:$T, $TARGET, &$NAME);
+    };
+    ($T:ty, $(#[$meta:meta])* $vis:vis static $NAME:ident: $TARGET:ty = $INIT:expr) => {
+        $crate::assoc_static!(():$T, $(#[$meta])* $vis static $NAME: $TARGET = $INIT);
+    };
+    ($TAG:ty:$T:ty, $TARGET:ty = static $STATIC:path) => {
+        $crate::assoc_static!(@instance);
+        $crate::assoc_static!(@impl $TAG:$T, $TARGET, &$STATIC);
+    };
+    ($T:ty, $TARGET:ty = static $STATIC:path) => {
+        $crate::assoc_static!(():$T, $TARGET = static $STATIC);
+    };
+    ($TAG:ty:$T:ty, $TARGET:ty = const { $($BODY:tt)* } $(; $($REST:tt)*)?) => {
+        $crate::assoc_static!($TAG:$T, $TARGET = { const { $($BODY)* } } $(; $($REST)*)?);
+    };
+    ($T:ty, $TARGET:ty = const { $($BODY:tt)* } $(; $($REST:tt)*)?) => {
+        $crate::assoc_static!(():$T, $TARGET = const { $($BODY)* } $(; $($REST)*)?);
+    };
+    ($TAG:ty:$T:ty, String = obfuscated $LIT:literal) => {
+        $crate::assoc_static!(@obfuscated $TAG:$T, String, $LIT.as_bytes(), |bytes| {
+            String::from_utf8(bytes).expect("obfuscated string literal")
+        });
+    };
+    ($TAG:ty:$T:ty, Vec<u8> = obfuscated $LIT:literal) => {
+        $crate::assoc_static!(@obfuscated $TAG:$T, Vec<u8>, $LIT, |bytes| bytes);
+    };
+    ($T:ty, $TARGET:ident $(<$ARG:ty>)? = obfuscated $LIT:literal) => {
+        $crate::assoc_static!(():$T, $TARGET $(<$ARG>)? = obfuscated $LIT);
+    };
+    (@obfuscated $TAG:ty:$T:ty, $TARGET:ty, $BYTES:expr, $DECODE:expr) => {
+        $crate::assoc_static!(@instance);
+        const _: () = {
+            const BYTES: &[u8] = $BYTES;
+            const KEY: u64 = $crate::stable_id(&[file!(), stringify!($T), stringify!($TAG)]);
+            static ENCODED: [u8; BYTES.len()] = $crate::obfuscate(BYTES, KEY);
+            $crate::assoc_static!(
+                @boxed $TAG:$T,
+                $TARGET = ($DECODE)($crate::deobfuscate(&ENCODED, KEY));
+            );
+        };
+    };
+    ($TAG:ty:$T:ty, $TARGET:ty = $INIT:expr; boxed $(; $CHECK:ident = $VALIDATE:expr)?) => {
+        $crate::assoc_static!(@instance);
+        $crate::assoc_static!(@boxed $TAG:$T, $TARGET = $INIT; $($CHECK = $VALIDATE)?);
+    };
+    (@boxed $TAG:ty:$T:ty, $TARGET:ty = $INIT:expr; $($CHECK:ident = $VALIDATE:expr)?) => {
+        impl $crate::AssocStatic<$TARGET, $TAG> for $T {
+            fn get_static() -> &'static $TARGET {
+                $crate::assoc_static_hit!($TAG:$T, $TARGET);
+                static ASSOCIATED_STATIC: std::sync::OnceLock<std::boxed::Box<$TARGET>> =
+                    std::sync::OnceLock::new();
+                if let Some(value) = ASSOCIATED_STATIC.get() {
+                    return value;
+                }
+                let _guard = $crate::InitGuard::new::<$T, $TARGET, $TAG>();
+                ASSOCIATED_STATIC.get_or_init(|| {
+                    let value = std::boxed::Box::new($INIT);
+                    $($crate::assoc_static!(@$CHECK $TAG:$T, $TARGET, &value, $VALIDATE);)?
+                    value
+                })
+            }
+            $crate::assoc_static!(@descriptor $TAG:$T, $TARGET);
+        }
+    };
+    ($T:ty, $TARGET:ty = $INIT:expr; boxed $(; $CHECK:ident = $VALIDATE:expr)?) => {
+        $crate::assoc_static!(():$T, $TARGET = $INIT; boxed $(; $CHECK = $VALIDATE)?);
+    };
+    ($TAG:ty:$T:ty, $TARGET:ty = $INIT:expr; racy) => {
+        $crate::assoc_static!(@instance);
+        impl $crate::AssocStatic<$TARGET, $TAG> for $T {
+            fn get_static() -> &'static $TARGET {
+                $crate::assoc_static_hit!($TAG:$T, $TARGET);
+                static ASSOCIATED_STATIC: $crate::RacyCell<$TARGET> = $crate::RacyCell::new();
+                ASSOCIATED_STATIC.get_or_init(|| $INIT)
+            }
+            $crate::assoc_static!(@descriptor $TAG:$T, $TARGET);
+        }
+    };
+    ($T:ty, $TARGET:ty = $INIT:expr; racy) => {
+        $crate::assoc_static!(():$T, $TARGET = $INIT; racy);
+    };
+    ($TAG:ty:$T:ty, $TARGET:ty = $INIT:expr; take) => {
+        impl $crate::AssocTake<$TARGET, $TAG> for $T {
+            fn take_static() -> Option<&'static mut $TARGET> {
+                static ASSOCIATED_STATIC: $crate::TakeCell<$TARGET> =
+                    $crate::TakeCell::new($crate::assoc_static!(@self $T, $TARGET = $INIT));
+                ASSOCIATED_STATIC.take()
+            }
+        }
+    };
+    ($T:ty, $TARGET:ty = $INIT:expr; take) => {
+        $crate::assoc_static!(():$T, $TARGET = $INIT; take);
+    };
+    ($TAG:ty:$T:ty, $TARGET:ty = $INIT:expr; sync(mutex)) => {
+        $crate::assoc_static!($TAG:$T, std::sync::Mutex<$TARGET> = std::sync::Mutex::new($INIT));
+    };
+    ($TAG:ty:$T:ty, $TARGET:ty = $INIT:expr; sync(rwlock)) => {
+        $crate::assoc_static!(
+            $TAG:$T,
+            std::sync::RwLock<$TARGET> = std::sync::RwLock::new($INIT)
+        );
+    };
+    ($T:ty, $TARGET:ty = $INIT:expr; sync($LOCK:ident)) => {
+        $crate::assoc_static!(():$T, $TARGET = $INIT; sync($LOCK));
+    };
+    ($TAG:ty:$T:ty, $TARGET:ty = $INIT:expr; debug_only) => {
+        #[cfg(debug_assertions)]
+        $crate::assoc_static!($TAG:$T, Option<$TARGET> = Some($INIT));
+        #[cfg(not(debug_assertions))]
+        $crate::assoc_static!($TAG:$T, Option<$TARGET> = None);
+    };
+    ($T:ty, $TARGET:ty = $INIT:expr; debug_only) => {
+        $crate::assoc_static!(():$T, $TARGET = $INIT; debug_only);
+    };
+    ($TAG:ty:$T:ty, $TARGET:ty = $INIT:expr; also_default) => {
+        $crate::assoc_static!($TAG:$T, $TARGET = $INIT);
+        $crate::assoc_static!(
+            @impl ():$T, $TARGET, <$T as $crate::AssocStaticRef<$TARGET, $TAG>>::STATIC_REF
+        );
+    };
+    ($TAG:ty:$T:ty, $TARGET:ty = $INIT:expr; cold $(= unsafe $SECTION:literal)?) => {
+        $crate::assoc_static!(
+            @plain [$(#[link_section = $SECTION])?] [#[cold] #[inline(never)]]
+            $TAG:$T, $TARGET = $INIT
+        );
+    };
+    ($T:ty, $TARGET:ty = $INIT:expr; cold $(= unsafe $SECTION:literal)?) => {
+        $crate::assoc_static!(():$T, $TARGET = $INIT; cold $(= unsafe $SECTION)?);
+    };
+    ($TAG:ty:$T:ty, $TARGET:ty = $INIT:expr) => {
+        $crate::assoc_static!(@plain [] [] $TAG:$T, $TARGET = $INIT);
+    };
+    (
+        @plain [$(#[$STATIC_ATTR:meta])*] [$(#[$FN_ATTR:meta])*]
+        $TAG:ty:$T:ty, $TARGET:ty = $INIT:expr
+    ) => {
+        $crate::assoc_static!(@instance);
+        const _: () = {
+            $(#[$STATIC_ATTR])*
+            static ASSOCIATED_STATIC: $TARGET = $crate::assoc_static!(@self $T, $TARGET = $INIT);
+            $crate::assoc_static!(@impl $(#[$FN_ATTR])* $TAG:$T, $TARGET, &ASSOCIATED_STATIC);
+        };
+    };
+    ($T:ty, $TARGET:ty = $INIT:expr) => {
+        $crate::assoc_static!(():$T, $TARGET = $INIT);
+    };
+    ($TAG:ty:$($T:ty)|+, $TARGET:ty = $INIT:expr) => {
+        $crate::assoc_static!(@instance);
+        const _: () = {
+            static ASSOCIATED_STATIC: $TARGET = $INIT;
+            $($crate::assoc_static!(@impl $TAG:$T, $TARGET, &ASSOCIATED_STATIC);)+
+        };
+    };
+    ($($T:ty)|+, $TARGET:ty = $INIT:expr) => {
+        $crate::assoc_static!(():$($T)|+, $TARGET = $INIT);
+    };
+}
+
+/// Expands to the `AssocStaticRef::STATIC_REF` of an association, this is usable in const
+/// contexts such as the initializers of other associations.
+///
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Example;
+/// struct Hello;
+/// assoc_static!(Hello:Example, &'static str = "Hello World!");
+///
+/// const GREETING: &str = *static_ref!(Hello:Example, &'static str);
+/// assert_eq!(GREETING, "Hello World!");
+/// ```
+#[macro_export]
+macro_rules! static_ref {
+    ($TAG:ty:$T:ty, $TARGET:ty) => {
+        <$T as $crate::AssocStaticRef<$TARGET, $TAG>>::STATIC_REF
+    };
+    ($T:ty, $TARGET:ty) => {
+        $crate::static_ref!(():$T, $TARGET)
+    };
+}
+
+/// Forwards associations from an inner type to a wrapper type.
+/// The associations are listed as 'TARGET' or 'TAG:TARGET', separated by commas.
+///
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Inner;
+/// struct Hello;
+/// assoc_static!(Inner, u32 = 42);
+/// assoc_static!(Hello:Inner, &'static str = "Hello World!");
+///
+/// struct Wrapper(Inner);
+/// assoc_delegate!(Wrapper => Inner; u32, Hello: &'static str);
+///
+/// assert_eq!(<Wrapper as AssocStatic<u32>>::get_static(), &42);
+/// assert_eq!(AssocStatic::<_, Hello>::assoc(&Wrapper(Inner)), &"Hello World!");
+/// ```
+#[macro_export]
+macro_rules! assoc_delegate {
+    ($T:ty => $INNER:ty;) => {};
+    ($T:ty => $INNER:ty; $TAG:ty: $TARGET:ty $(, $($REST:tt)*)?) => {
+        impl $crate::AssocStatic<$TARGET, $TAG> for $T {
+            fn get_static() -> &'static $TARGET {
+                <$INNER as $crate::AssocStatic<$TARGET, $TAG>>::get_static()
+            }
+
+            fn descriptor() -> $crate::Descriptor
+            where
+                Self: 'static,
+                $TARGET: 'static,
+                $TAG: 'static,
+            {
+                <$INNER as $crate::AssocStatic<$TARGET, $TAG>>::descriptor()
+            }
+        }
+        $crate::assoc_delegate!($T => $INNER; $($($REST)*)?);
+    };
+    ($T:ty => $INNER:ty; $TARGET:ty $(, $($REST:tt)*)?) => {
+        $crate::assoc_delegate!($T => $INNER; (): $TARGET $(, $($REST)*)?);
+    };
+}
+
+/// Associates a `String` that is built with `format!()` on first access.
+/// This is useful for version banners and other derived strings that can not be created
+/// by a const initializer.
+///
+/// ```
+/// use crate::assoc_static::*;
+///
+/// const MAJOR: u32 = 1;
+/// const MINOR: u32 = 2;
+///
+/// struct Example;
+/// assoc_static_fmt!(Example, "v{}.{}", MAJOR, MINOR);
+///
+/// assert_eq!(<Example as AssocStatic<String>>::get_static(), "v1.2");
+/// ```
+#[macro_export]
+macro_rules! assoc_static_fmt {
+    ($TAG:ty:$T:ty, $($FMT:tt)+) => {
+        $crate::assoc_static!(@instance);
+        impl $crate::AssocStatic<std::string::String, $TAG> for $T {
+            fn get_static() -> &'static std::string::String {
+                $crate::assoc_static_hit!($TAG:$T, std::string::String);
+                static ASSOCIATED_STATIC: std::sync::OnceLock<std::string::String> =
+                    std::sync::OnceLock::new();
+                if let Some(value) = ASSOCIATED_STATIC.get() {
+                    return value;
+                }
+                let _guard = $crate::InitGuard::new::<$T, std::string::String, $TAG>();
+                ASSOCIATED_STATIC.get_or_init(|| format!($($FMT)+))
+            }
+            $crate::assoc_static!(@descriptor $TAG:$T, std::string::String);
+        }
+    };
+    ($T:ty, $($FMT:tt)+) => {
+        $crate::assoc_static_fmt!(():$T, $($FMT)+);
+    };
+}
+
+/// Declares a table of associations for one type. Every row defines a new tag, declared with
+/// `tags!()`, and associates the given static object with that tag. The type also gets a
+/// `HasTags` impl listing all tags of the table, thus there can be only one table per type.
+///
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Example;
+/// assoc_map!(Example {
+///     pub Hello: &'static str = "Hello World!",
+///     pub ExplainType: &'static str = "This is 'struct Example'",
+///     pub Answer: u32 = 42,
+/// });
+///
+/// assert_eq!(Hello::of::<Example>(), &"Hello World!");
+/// assert_eq!(AssocStatic::<u32, Answer>::assoc(&Example), &42);
+/// assert_eq!(Example::TAGS.len(), 3);
+/// ```
+#[macro_export]
+macro_rules! assoc_map {
+    ($T:ty { $($(#[$meta:meta])* $vis:vis $TAG:ident: $TARGET:ty = $INIT:expr),* $(,)? }) => {
+        $crate::tags! {
+            $($(#[$meta])* $vis $TAG: $TARGET;)*
+        }
+        $($crate::assoc_static!($TAG:$T, $TARGET = $INIT);)*
+        $crate::has_tags!($T: $($TAG),*);
+    };
+}
+
+/// Associates a static object of the same type with many types under one tag, e.g. the
+/// default style of each widget type for a theme. Switching the theme tag switches all
+/// styles at once:
+///
+/// ```
+/// use crate::assoc_static::*;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Style {
+///     background: u32,
+///     rounding: u8,
+/// }
+///
+/// struct Button;
+/// struct Label;
+///
+/// tags! {
+///     Light;
+///     Dark;
+/// }
+///
+/// assoc_theme!(Light: Style {
+///     Button = Style { background: 0xffffff, rounding: 4 },
+///     Label = Style { background: 0xeeeeee, rounding: 0 },
+/// });
+/// assoc_theme!(Dark: Style {
+///     Button = Style { background: 0x202020, rounding: 4 },
+///     Label = Style { background: 0x101010, rounding: 0 },
+/// });
+///
+/// fn background<THEME, W: AssocStatic<Style, THEME>>() -> u32 {
+///     W::get_static().background
+/// }
+///
+/// assert_eq!(background::<Dark, Button>(), 0x202020);
+/// assert_eq!(background::<Light, Label>(), 0xeeeeee);
+/// ```
+#[macro_export]
+macro_rules! assoc_theme {
+    ($TAG:ty: $TARGET:ty { $($T:ty = $INIT:expr),* $(,)? }) => {
+        $($crate::assoc_static!($TAG:$T, $TARGET = $INIT);)*
+    };
+}
+
+/// Declares a tag as alias of another tag. Lookups through either tag resolve to the same
+/// static object for all types. This helps renaming tags while downstream crates still use
+/// the old name. Attributes such as `#[deprecated]` are passed through to the alias.
+///
+/// The alias is a plain type alias, a blanket `AssocStatic` impl for all types can not be
+/// written outside of this crate because of the orphan rules.
+///
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Example;
+/// pub struct Greeting;
+/// assoc_static!(Greeting:Example, &'static str = "Hello World!");
+///
+/// // 'Hello' was the old name of the 'Greeting' tag
+/// assoc_tag_alias!(pub Hello = Greeting);
+///
+/// let example = Example;
+/// assert_eq!(AssocStatic::<_, Hello>::assoc(&example), &"Hello World!");
+/// assert_eq!(AssocStatic::<_, Greeting>::assoc(&example), &"Hello World!");
+/// ```
+#[macro_export]
+macro_rules! assoc_tag_alias {
+    ($(#[$meta:meta])* $vis:vis $NEW:ident = $OLD:ty) => {
+        $(#[$meta])*
+        $vis type $NEW = $OLD;
+    };
+}
+
+/// Fails compilation when an association does not exist. This lets library authors state
+/// their integration requirements where downstream users see them.
+///
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Example;
+/// struct Hello;
+/// assoc_static!(Hello:Example, &'static str = "Hello World!");
+///
+/// assert_assoc!(Hello:Example, &'static str);
+/// ```
+///
+/// ```compile_fail
+/// use crate::assoc_static::*;
+///
+/// struct Example;
+/// assert_assoc!(Example, u32);
+/// ```
+#[macro_export]
+macro_rules! assert_assoc {
+    ($TAG:ty:$T:ty, $TARGET:ty) => {
+        const _: () = $crate::assert_assoc_helper::<$T, $TARGET, $TAG>();
+    };
+    ($T:ty, $TARGET:ty) => {
+        $crate::assert_assoc!(():$T, $TARGET);
+    };
+}
+
+/// Only a helper, needs to be public because of the macro
+#[doc(hidden)]
+#[track_caller]
+pub fn validate_helper<T: ?Sized, TARGET, TAG, E: std::fmt::Display>(
+    value: &TARGET,
+    validator: impl FnOnce(&TARGET) -> Result<(), E>,
+) {
+    if let Err(error) = validator(value) {
+        panic!(
+            "invalid associated static '{}' of '{}' with tag '{}': {error}",
+            std::any::type_name::<TARGET>(),
+            std::any::type_name::<T>(),
+            std::any::type_name::<TAG>(),
+        );
+    }
+}
+
+/// Only a helper, needs to be public because of the macro
+#[doc(hidden)]
+pub const fn assert_assoc_helper<T: AssocStatic<TARGET, TAG> + ?Sized, TARGET, TAG>() {}
+
+/// Only a helper, needs to be public because of the macro
+#[cfg(feature = "stats")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! assoc_static_hit {
+    ($TAG:ty:$T:ty, $TARGET:ty) => {{
+        static COUNTER: $crate::stats::Counter = $crate::stats::Counter::new();
+        COUNTER.hit(<$T as $crate::AssocStatic<$TARGET, $TAG>>::descriptor);
+    }};
+}
+
+/// Only a helper, needs to be public because of the macro
+#[cfg(not(feature = "stats"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! assoc_static_hit {
+    ($TAG:ty:$T:ty, $TARGET:ty) => {};
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::AssocStatic;
+    use crate::{AssocStatic, AssocStaticUntagged};
 
     struct TestType1;
     assoc_static!(TestType1, &'static str = "This is the first test type");
@@ -137,16 +1287,431 @@ mod tests {
     #[test]
     fn from_instance() {
         let test = TestType1;
-        assert_eq!(AssocStatic::from(&test), &"This is the first test type");
+        assert_eq!(test.assoc(), &"This is the first test type");
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn deprecated_from() {
+        assert_eq!(
+            AssocStatic::from(&TestType1),
+            &"This is the first test type"
+        );
     }
 
     #[test]
     fn from_instance_multiple() {
         let test = TestType2;
         assert_eq!(
-            AssocStatic::<&str, _>::from(&test),
+            AssocStatic::<&str, _>::assoc(&test),
             &"This is the second test type"
         );
-        assert_eq!(*AssocStatic::<u32, _>::from(&test), 42);
+        assert_eq!(*AssocStatic::<u32, _>::assoc(&test), 42);
+    }
+
+    struct TestTag;
+    struct TestType3;
+    assoc_static!(TestTag:TestType3, u32 = 23; also_default);
+
+    #[test]
+    fn also_default() {
+        let tagged = <TestType3 as AssocStatic<u32, TestTag>>::get_static();
+        let untagged = <TestType3 as AssocStatic<u32, ()>>::get_static();
+        assert_eq!(*tagged, 23);
+        assert!(std::ptr::eq(tagged, untagged));
+    }
+
+    struct TestType4;
+    assoc_static!(TestTag:TestType4, [u8, &'static str, Option<u32>] = (1, "two", Some(3)));
+
+    #[test]
+    fn multiple_targets() {
+        assert_eq!(*<TestType4 as AssocStatic<u8, TestTag>>::get_static(), 1);
+        assert_eq!(
+            *<TestType4 as AssocStatic<&str, TestTag>>::get_static(),
+            "two"
+        );
+        assert_eq!(
+            *<TestType4 as AssocStatic<Option<u32>, TestTag>>::get_static(),
+            Some(3)
+        );
+    }
+
+    struct TestType5;
+    assoc_static_fmt!(TestTag:TestType5, "{} is {}", "fmt", 42);
+
+    #[test]
+    fn formatted() {
+        let formatted = <TestType5 as AssocStatic<String, TestTag>>::get_static();
+        assert_eq!(formatted, "fmt is 42");
+        assert!(std::ptr::eq(
+            formatted,
+            <TestType5 as AssocStatic<String, TestTag>>::get_static()
+        ));
+    }
+
+    #[test]
+    fn descriptor() {
+        let descriptor = <TestType4 as AssocStatic<u8, TestTag>>::descriptor();
+        assert!(descriptor.tag_name.ends_with("TestTag"));
+        assert_eq!(descriptor.target_id, std::any::TypeId::of::<u8>());
+        assert_eq!(descriptor.align, 1);
+        assert_eq!(descriptor.location.unwrap().file, file!());
+
+        let descriptor = <TestType3 as AssocStatic<u32, ()>>::descriptor();
+        assert_eq!(descriptor.tag_name, "()");
+        assert_eq!(descriptor.size, 4);
+    }
+
+    #[test]
+    fn static_ref() {
+        use crate::AssocStaticRef;
+        const TAGGED: &u32 = <TestType3 as AssocStaticRef<u32, TestTag>>::STATIC_REF;
+        const UNTAGGED: &u32 = <TestType3 as AssocStaticRef<u32, ()>>::STATIC_REF;
+        const MULTI: &&str = <TestType4 as AssocStaticRef<&str, TestTag>>::STATIC_REF;
+        assert!(std::ptr::eq(TAGGED, UNTAGGED));
+        assert!(std::ptr::eq(
+            TAGGED,
+            <TestType3 as AssocStatic<u32, TestTag>>::get_static()
+        ));
+        assert_eq!(*MULTI, "two");
+    }
+
+    trait TestTrait {}
+    assoc_static!(dyn TestTrait, u32 = 118);
+    assoc_static!(TestTag:dyn TestTrait, [u8, u16] = (1, 2));
+
+    #[test]
+    fn trait_object() {
+        assert_eq!(*<dyn TestTrait as AssocStatic<u32>>::get_static(), 118);
+        assert_eq!(
+            *<dyn TestTrait as AssocStatic<u16, TestTag>>::get_static(),
+            2
+        );
+        assert!(<dyn TestTrait as AssocStatic<u32>>::descriptor()
+            .type_name
+            .contains("TestTrait"));
+    }
+
+    #[test]
+    fn phantom_data() {
+        use std::marker::PhantomData;
+        assert_eq!(
+            *<PhantomData<TestType3> as AssocStatic<u32, TestTag>>::get_static(),
+            23
+        );
+        assert_eq!(
+            <PhantomData<dyn TestTrait> as AssocStatic<u32>>::descriptor(),
+            <dyn TestTrait as AssocStatic<u32>>::descriptor()
+        );
+    }
+
+    struct TestType6;
+    assoc_static!(TestTag:TestType6, [u8; 1 << 16] = [7; 1 << 16]; boxed);
+
+    #[test]
+    fn boxed() {
+        let boxed = <TestType6 as AssocStatic<[u8; 1 << 16], TestTag>>::get_static();
+        assert_eq!(boxed[1000], 7);
+        assert!(std::ptr::eq(
+            boxed,
+            <TestType6 as AssocStatic<[u8; 1 << 16], TestTag>>::get_static()
+        ));
+    }
+
+    struct TestType7;
+    struct TestType8;
+    assoc_static!(TestType7 | TestType8 | dyn TestTrait, &'static str = "shared");
+
+    #[test]
+    fn shared() {
+        let first = <TestType7 as AssocStatic<&str>>::get_static();
+        assert_eq!(*first, "shared");
+        assert!(std::ptr::eq(
+            first,
+            <TestType8 as AssocStatic<&str>>::get_static()
+        ));
+        assert!(std::ptr::eq(
+            first,
+            <dyn TestTrait as AssocStatic<&str>>::get_static()
+        ));
+    }
+
+    struct TestType9;
+    assoc_static!(TestTag:TestType9, u32 = cfg {
+        test => 1,
+        _ => 2,
+    });
+    assoc_static!(TestType9, u32 = cfg {
+        not(test) => 1,
+        any(test, unix) => 2,
+        test => 3,
+    });
+
+    #[test]
+    fn cfg_init() {
+        assert_eq!(*<TestType9 as AssocStatic<u32, TestTag>>::get_static(), 1);
+        assert_eq!(*<TestType9 as AssocStatic<u32, ()>>::get_static(), 2);
+    }
+
+    struct TestType10;
+    assoc_static!(TestType10, Vec<u8> = vec![1, 2, 3]; boxed; validate = |v: &Vec<u8>| {
+        if v.is_empty() {
+            Err("empty")
+        } else {
+            Ok(())
+        }
+    });
+    assoc_static!(TestTag:TestType10, u8 = 0; boxed; debug_validate = |v: &u8| match v {
+        0 => Err(format!("{v} is zero")),
+        _ => Ok(()),
+    });
+
+    #[test]
+    fn validate() {
+        assert_eq!(<TestType10 as AssocStatic<Vec<u8>>>::get_static().len(), 3);
+    }
+
+    #[test]
+    #[cfg_attr(debug_assertions, should_panic(expected = "0 is zero"))]
+    fn debug_validate() {
+        <TestType10 as AssocStatic<u8, TestTag>>::get_static();
+    }
+
+    struct TestType11;
+    assoc_map!(TestType11 {
+        /// documented tag
+        First: u8 = 1,
+        pub(crate) Second: &'static str = "second",
+    });
+
+    #[test]
+    fn table() {
+        use crate::HasTags;
+        assert_eq!(*First::of::<TestType11>(), 1);
+        assert_eq!(*Second::of::<TestType11>(), "second");
+        assert!(TestType11::has_tag::<First>());
+        assert!(TestType11::has_tag::<Second>());
+    }
+
+    struct TestType12;
+    assoc_static!(TestTag:TestType12, [u32; 4] = const {
+        const START: u32 = 1;
+        [START, START + 1, START + 2, START + 3]
+    }; boxed);
+    assoc_static!(
+        TestType12,
+        u32 = const {
+            const BASE: u32 = 3;
+            BASE * BASE
+        }
+    );
+
+    #[test]
+    fn const_block() {
+        assert_eq!(
+            <TestType12 as AssocStatic<[u32; 4], TestTag>>::get_static(),
+            &[1, 2, 3, 4]
+        );
+        assert_eq!(*<TestType12 as AssocStatic<u32>>::get_static(), 9);
+    }
+
+    mod legacy {
+        pub static TABLE: [u8; 3] = [1, 2, 3];
+    }
+    struct TestType13;
+    assoc_static!(TestTag:TestType13, [u8; 3] = static legacy::TABLE);
+
+    #[test]
+    fn existing_static() {
+        assert!(std::ptr::eq(
+            <TestType13 as AssocStatic<[u8; 3], TestTag>>::get_static(),
+            &legacy::TABLE
+        ));
+        assert_eq!(static_ref!(TestTag:TestType13, [u8; 3]), &[1, 2, 3]);
+    }
+
+    struct TestType14;
+    assoc_static!(TestTag:TestType14,
+        /// documented
+        pub(crate) static TEST_TYPE14_NAME: &'static str = "fourteen"
+    );
+
+    #[test]
+    fn named_static() {
+        assert_eq!(TEST_TYPE14_NAME, "fourteen");
+        assert!(std::ptr::eq(
+            <TestType14 as AssocStatic<&str, TestTag>>::get_static(),
+            &TEST_TYPE14_NAME
+        ));
+    }
+
+    struct TestType15(#[allow(dead_code)] u16);
+    assoc_static!(TestType15, usize = std::mem::align_of::<Self>());
+    assoc_static!(TestTag:TestType15, String = std::any::type_name::<Self>().into(); boxed);
+
+    #[test]
+    fn self_in_init() {
+        assert_eq!(*<TestType15 as AssocStatic<usize>>::get_static(), 2);
+        assert!(<TestType15 as AssocStatic<String, TestTag>>::get_static().ends_with("TestType15"));
+    }
+
+    struct TestType16<T>(std::marker::PhantomData<T>);
+    assoc_static!(for<P in [u8, (), String]> TestTag:TestType16<P>, usize = size_of::<P>());
+
+    #[test]
+    fn instantiation_list() {
+        assert_eq!(
+            *<TestType16<u8> as AssocStatic<usize, TestTag>>::get_static(),
+            1
+        );
+        assert_eq!(
+            *<TestType16<()> as AssocStatic<usize, TestTag>>::get_static(),
+            0
+        );
+        assert_eq!(
+            *<TestType16<String> as AssocStatic<usize, TestTag>>::get_static(),
+            size_of::<String>()
+        );
+    }
+
+    #[test]
+    fn instantiation_ids() {
+        let u8_id = <TestType16<u8> as AssocStatic<usize, TestTag>>::id();
+        let unit_id = <TestType16<()> as AssocStatic<usize, TestTag>>::id();
+        let string_id = <TestType16<String> as AssocStatic<usize, TestTag>>::id();
+        assert_ne!(u8_id, unit_id);
+        assert_ne!(u8_id, string_id);
+        assert_ne!(unit_id, string_id);
+        let parts = [
+            concat!(module_path!(), "::TestType16<P>"),
+            "TestTag",
+            "usize",
+            "P",
+            "u8",
+        ];
+        assert_eq!(u8_id, crate::stable_id(&parts));
+    }
+
+    #[test]
+    fn plain_static() {
+        use crate::AssocStaticRef;
+        let value = <TestType1 as AssocStatic<&str>>::get_static();
+        assert!(std::ptr::eq(
+            value,
+            <TestType1 as AssocStaticRef<&str>>::STATIC_REF
+        ));
+        assert!(std::ptr::eq(
+            value,
+            <TestType1 as AssocStatic<&str>>::get_static()
+        ));
+    }
+
+    struct TestType17;
+    assoc_static!(TestType17, &'static str = "cold"; cold);
+    // SAFETY: a plain array is fine in a read-only section
+    #[cfg(target_os = "linux")]
+    assoc_static!(TestTag:TestType17, [u32; 4] = [1, 2, 3, 4]; cold = unsafe ".rodata.assoc_cold");
+
+    #[test]
+    fn cold() {
+        assert_eq!(*<TestType17 as AssocStatic<&str>>::get_static(), "cold");
+        #[cfg(target_os = "linux")]
+        assert_eq!(
+            <TestType17 as AssocStatic<[u32; 4], TestTag>>::get_static(),
+            &[1, 2, 3, 4]
+        );
+    }
+
+    struct TestType18;
+    assoc_static!(TestType18, String = obfuscated "string");
+    assoc_static!(TestTag:TestType18, Vec<u8> = obfuscated b"\x00bytes");
+
+    #[test]
+    fn obfuscated() {
+        assert_eq!(<TestType18 as AssocStatic<String>>::get_static(), "string");
+        assert_eq!(
+            <TestType18 as AssocStatic<Vec<u8>, TestTag>>::get_static(),
+            b"\x00bytes"
+        );
+    }
+
+    struct TestType19;
+    struct TestType20;
+    assoc_theme!(TestTag: &'static str {
+        TestType19 = "nineteen",
+        TestType20 = "twenty",
+    });
+
+    #[test]
+    fn theme() {
+        assert_eq!(
+            *<TestType19 as AssocStatic<&str, TestTag>>::get_static(),
+            "nineteen"
+        );
+        assert_eq!(
+            *<TestType20 as AssocStatic<&str, TestTag>>::get_static(),
+            "twenty"
+        );
+    }
+
+    struct TestType21;
+    assoc_static!(TestType21, u32 = 21);
+    assoc_static!(TestTag:TestType21, u32 = 210);
+
+    fn untagged<V: AssocStaticUntagged<u32>>() -> u32 {
+        *V::get_untagged()
+    }
+
+    #[test]
+    fn untagged_subtrait() {
+        assert_eq!(untagged::<TestType21>(), 21);
+    }
+
+    struct TestType22;
+    assoc_static!(TestType22, [u8; 64] = [22; 64]; debug_only);
+
+    #[test]
+    fn debug_only() {
+        let table = <TestType22 as AssocStatic<Option<[u8; 64]>>>::get_static();
+        assert_eq!(table.is_some(), cfg!(debug_assertions));
+    }
+
+    trait Init {
+        const N: u32;
+    }
+
+    struct TestType23(#[allow(dead_code)] [u8; 23]);
+    impl Init for TestType23 {
+        const N: u32 = 23;
+    }
+    assoc_static!(TestType23, u32 = <TestType23 as Init>::N);
+    assoc_static!(TestTag:TestType23, [usize, u32] = (std::mem::size_of::<Self>(), Self::N));
+    assoc_static!(TestType23, static TEST_TYPE23: usize = std::mem::size_of::<Self>());
+    assoc_static!(TestTag:TestType23, u8 = const { std::mem::size_of::<Self>() as u8 });
+    assoc_static!(TestTag:TestType23, u16 = std::mem::size_of::<Self>() as u16; take);
+
+    #[test]
+    fn self_in_all_forms() {
+        assert_eq!(*<TestType23 as AssocStatic<u32>>::get_static(), 23);
+        assert_eq!(
+            *<TestType23 as AssocStatic<usize, TestTag>>::get_static(),
+            23
+        );
+        assert_eq!(*<TestType23 as AssocStatic<u32, TestTag>>::get_static(), 23);
+        assert_eq!(TEST_TYPE23, 23);
+        assert_eq!(*<TestType23 as AssocStatic<u8, TestTag>>::get_static(), 23);
+        let taken = <TestType23 as crate::AssocTake<u16, TestTag>>::take_static();
+        assert_eq!(taken.copied(), Some(23));
+    }
+
+    struct TestType24;
+    assoc_static!(TestTag:TestType24, u32 = 24);
+
+    #[test]
+    fn id_from_spelled_paths() {
+        const EXPECTED: u64 =
+            crate::stable_id(&[concat!(module_path!(), "::TestType24"), "TestTag", "u32"]);
+        assert_eq!(<TestType24 as AssocStatic<u32, TestTag>>::id(), EXPECTED);
     }
 }
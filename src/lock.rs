@@ -0,0 +1,139 @@
+use std::sync::{Mutex, MutexGuard, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use crate::AssocStatic;
+
+/// Locks an association wrapped with `assoc_static!(TAG:T, TARGET = INIT; sync(mutex))`.
+/// Such associations implement `AssocStatic<Mutex<TARGET>, TAG>`, which makes 'TARGET'
+/// shareable as long as it is `Send`.
+///
+/// # Poisoning
+///
+/// A panic while the lock is held poisons it, `lock_static()` recovers the guard instead of
+/// panicking. A static has no owner that could decide what to do with a poisoned value, and
+/// panicking in every later access would take down unrelated users of the type. The poison
+/// flag is left set, thus code that cares can check `get_static().is_poisoned()`, repair the
+/// value and call `clear_poison()`.
+///
+/// ```
+/// use crate::assoc_static::*;
+/// use std::cell::Cell;
+///
+/// struct Example;
+/// assoc_static!(Example, Cell<u32> = Cell::new(1); sync(mutex));
+///
+/// <Example as AssocMutex<Cell<u32>>>::lock_static().set(2);
+/// assert_eq!(<Example as AssocMutex<Cell<u32>>>::lock_static().get(), 2);
+/// ```
+pub trait AssocMutex<T: 'static, TAG = ()> {
+    /// Locks the associated mutex.
+    fn lock_static() -> MutexGuard<'static, T>;
+}
+
+impl<T: 'static, TAG, V> AssocMutex<T, TAG> for V
+where
+    V: AssocStatic<Mutex<T>, TAG> + ?Sized,
+{
+    fn lock_static() -> MutexGuard<'static, T> {
+        V::get_static()
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Locks an association wrapped with `assoc_static!(TAG:T, TARGET = INIT; sync(rwlock))`.
+/// Such associations implement `AssocStatic<RwLock<TARGET>, TAG>`. Since readers share the
+/// value, 'TARGET' must be `Send + Sync`, use `sync(mutex)` for targets which are not `Sync`.
+/// Poisoned locks are recovered as described for `AssocMutex`.
+///
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Example;
+/// assoc_static!(Example, Vec<&'static str> = Vec::new(); sync(rwlock));
+///
+/// <Example as AssocRwLock<Vec<&str>>>::write_static().push("hello");
+/// assert_eq!(<Example as AssocRwLock<Vec<&str>>>::read_static()[0], "hello");
+/// ```
+pub trait AssocRwLock<T: 'static, TAG = ()> {
+    /// Locks the associated rwlock for reading.
+    fn read_static() -> RwLockReadGuard<'static, T>;
+
+    /// Locks the associated rwlock for writing.
+    fn write_static() -> RwLockWriteGuard<'static, T>;
+}
+
+impl<T: 'static, TAG, V> AssocRwLock<T, TAG> for V
+where
+    V: AssocStatic<RwLock<T>, TAG> + ?Sized,
+{
+    fn read_static() -> RwLockReadGuard<'static, T> {
+        V::get_static()
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write_static() -> RwLockWriteGuard<'static, T> {
+        V::get_static()
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::{Cell, RefCell};
+    use std::sync::Mutex;
+
+    use super::{AssocMutex, AssocRwLock};
+    use crate::{assoc_static, AssocStatic};
+
+    struct TestType;
+    struct TestTag;
+    assoc_static!(TestType, RefCell<u32> = RefCell::new(0); sync(mutex));
+    assoc_static!(TestTag:TestType, u32 = 1; sync(rwlock));
+
+    // 'Cell' is not 'Sync', the mutex makes it shareable
+    struct Counter;
+    assoc_static!(Counter, Cell<u32> = Cell::new(0); sync(mutex));
+
+    #[test]
+    fn non_sync_target() {
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                std::thread::spawn(|| {
+                    let counter = <Counter as AssocMutex<Cell<u32>>>::lock_static();
+                    counter.set(counter.get() + 1);
+                })
+            })
+            .collect();
+        threads
+            .into_iter()
+            .for_each(|thread| thread.join().unwrap());
+        assert_eq!(<Counter as AssocMutex<Cell<u32>>>::lock_static().get(), 4);
+    }
+
+    #[test]
+    fn poisoned() {
+        let _ = std::thread::spawn(|| {
+            let _guard = <TestType as AssocMutex<RefCell<u32>>>::lock_static();
+            panic!("poison the lock");
+        })
+        .join();
+        let mutex = <TestType as AssocStatic<Mutex<RefCell<u32>>>>::get_static();
+        assert!(mutex.is_poisoned());
+        *<TestType as AssocMutex<RefCell<u32>>>::lock_static().borrow_mut() += 1;
+        assert_eq!(
+            *<TestType as AssocMutex<RefCell<u32>>>::lock_static().borrow(),
+            1
+        );
+        assert!(mutex.is_poisoned());
+        mutex.clear_poison();
+        assert!(!mutex.is_poisoned());
+    }
+
+    #[test]
+    fn rwlock() {
+        *<TestType as AssocRwLock<u32, TestTag>>::write_static() += 1;
+        assert_eq!(*<TestType as AssocRwLock<u32, TestTag>>::read_static(), 2);
+    }
+}
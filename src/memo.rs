@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::{Mutex, OnceLock};
+
+use crate::AssocStatic;
+
+crate::tags! {
+    /// Tag for the `Memo` of a type, associated with `assoc_memo!()`.
+    pub MemoTag;
+}
+
+/// Results of a pure computation, each computed once per key and kept for the lifetime of
+/// the program. Concurrent callers with the same key wait for the first one, different keys
+/// are computed in parallel.
+#[derive(Debug)]
+pub struct Memo<K, V: 'static> {
+    results: Mutex<Option<HashMap<K, &'static OnceLock<V>>>>,
+}
+
+impl<K, V> Memo<K, V> {
+    /// Creates an empty memo.
+    pub const fn new() -> Self {
+        Memo {
+            results: Mutex::new(None),
+        }
+    }
+
+    /// Returns the memo associated with 'T'.
+    pub fn of<T: AssocStatic<Self, MemoTag> + ?Sized>() -> &'static Self {
+        T::get_static()
+    }
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Hash + Eq, V> Memo<K, V> {
+    /// Returns the result for 'key', computing it with 'compute' on the first call.
+    pub fn get_or_compute(&self, key: K, compute: impl FnOnce() -> V) -> &'static V {
+        let cell = *self
+            .results
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .get_or_insert_with(HashMap::new)
+            .entry(key)
+            .or_insert_with(|| Box::leak(Box::new(OnceLock::new())));
+        cell.get_or_init(compute)
+    }
+
+    /// Returns the number of memoized keys.
+    pub fn len(&self) -> usize {
+        self.results
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .as_ref()
+            .map_or(0, HashMap::len)
+    }
+
+    /// Returns true when nothing was memoized yet.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Associates a `Memo` with a type and adds an inherent `memoized()` function, which
+/// evaluates the body once per set of arguments. The arguments must be
+/// `Clone + Hash + Eq + Send`, they are cloned into the key.
+///
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Grammar;
+/// assoc_memo!(Grammar, fn(rule: String, depth: u32) -> String {
+///     format!("{rule}/{depth}")
+/// });
+///
+/// let first = Grammar::memoized("expr".into(), 2);
+/// assert_eq!(first, "expr/2");
+/// assert!(std::ptr::eq(first, Grammar::memoized("expr".into(), 2)));
+/// assert_eq!(Memo::<(String, u32), String>::of::<Grammar>().len(), 1);
+/// ```
+#[macro_export]
+macro_rules! assoc_memo {
+    ($T:ty, fn($($ARG:ident: $A:ty),* $(,)?) -> $R:ty $BODY:block) => {
+        $crate::assoc_static!(
+            $crate::MemoTag:$T,
+            $crate::Memo<($($A,)*), $R> = $crate::Memo::new()
+        );
+
+        impl $T {
+            /// Returns the memoized result for the given arguments.
+            #[allow(dead_code)]
+            pub fn memoized($($ARG: $A),*) -> &'static $R {
+                $crate::Memo::<($($A,)*), $R>::of::<$T>()
+                    .get_or_compute(($(std::clone::Clone::clone(&$ARG),)*), move || $BODY)
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::Memo;
+
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    struct TestType;
+    assoc_memo!(TestType, fn(n: u64) -> u64 {
+        CALLS.fetch_add(1, Ordering::Relaxed);
+        (1..=n).product()
+    });
+
+    #[test]
+    fn computed_once() {
+        assert_eq!(*TestType::memoized(5), 120);
+        assert_eq!(*TestType::memoized(5), 120);
+        assert_eq!(*TestType::memoized(3), 6);
+        assert_eq!(CALLS.load(Ordering::Relaxed), 2);
+        assert_eq!(Memo::<(u64,), u64>::of::<TestType>().len(), 2);
+    }
+}
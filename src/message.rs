@@ -0,0 +1,156 @@
+use std::fmt;
+
+use crate::AssocStatic;
+
+crate::tags! {
+    /// Tag for the `MessageInfo` of a wire message type.
+    pub MessageTag: MessageInfo;
+}
+
+/// Header constants of a wire message type, associated with `#[derive(AssocMessage)]` or
+/// `assoc_static!(MessageTag:T, MessageInfo = ...)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MessageInfo {
+    /// Magic number starting each message
+    pub magic: u32,
+    /// Id of the message type
+    pub id: u16,
+    /// Minimum payload length
+    pub min_len: u32,
+    /// Maximum payload length
+    pub max_len: u32,
+}
+
+/// Length of the header written by `encode_header()`: magic, id and payload length, all
+/// little endian.
+pub const HEADER_LEN: usize = 10;
+
+/// Errors from `encode_header()` and `decode_header()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeaderError {
+    /// Less than `HEADER_LEN` bytes
+    Truncated,
+    /// The magic number does not match
+    Magic(u32),
+    /// The message id does not match
+    MessageId(u16),
+    /// The payload length is out of the allowed range
+    Length(u32),
+}
+
+impl fmt::Display for HeaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HeaderError::Truncated => write!(f, "truncated message header"),
+            HeaderError::Magic(magic) => write!(f, "unexpected magic number {magic:#x}"),
+            HeaderError::MessageId(id) => write!(f, "unexpected message id {id}"),
+            HeaderError::Length(len) => write!(f, "payload length {len} out of range"),
+        }
+    }
+}
+
+impl std::error::Error for HeaderError {}
+
+impl MessageInfo {
+    fn check_len(&self, len: u32) -> Result<(), HeaderError> {
+        if (self.min_len..=self.max_len).contains(&len) {
+            Ok(())
+        } else {
+            Err(HeaderError::Length(len))
+        }
+    }
+}
+
+/// Returns the header for a message of type 'T' with a payload of 'len' bytes.
+///
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Ping;
+/// assoc_static!(MessageTag:Ping, MessageInfo = MessageInfo {
+///     magic: 0xcafe,
+///     id: 1,
+///     min_len: 0,
+///     max_len: 8,
+/// });
+///
+/// let header = encode_header::<Ping>(4).unwrap();
+/// assert_eq!(decode_header::<Ping>(&header), Ok(4));
+/// assert_eq!(encode_header::<Ping>(9), Err(HeaderError::Length(9)));
+/// ```
+pub fn encode_header<T>(len: usize) -> Result<[u8; HEADER_LEN], HeaderError>
+where
+    T: AssocStatic<MessageInfo, MessageTag> + ?Sized,
+{
+    let info = T::get_static();
+    let len = u32::try_from(len).map_err(|_| HeaderError::Length(u32::MAX))?;
+    info.check_len(len)?;
+    let mut header = [0; HEADER_LEN];
+    header[0..4].copy_from_slice(&info.magic.to_le_bytes());
+    header[4..6].copy_from_slice(&info.id.to_le_bytes());
+    header[6..10].copy_from_slice(&len.to_le_bytes());
+    Ok(header)
+}
+
+/// Validates the header at the start of 'bytes' as header of a message of type 'T' and
+/// returns the payload length.
+pub fn decode_header<T>(bytes: &[u8]) -> Result<usize, HeaderError>
+where
+    T: AssocStatic<MessageInfo, MessageTag> + ?Sized,
+{
+    let info = T::get_static();
+    let header = bytes.get(..HEADER_LEN).ok_or(HeaderError::Truncated)?;
+    let magic = u32::from_le_bytes(header[0..4].try_into().expect("4 bytes"));
+    if magic != info.magic {
+        return Err(HeaderError::Magic(magic));
+    }
+    let id = u16::from_le_bytes(header[4..6].try_into().expect("2 bytes"));
+    if id != info.id {
+        return Err(HeaderError::MessageId(id));
+    }
+    let len = u32::from_le_bytes(header[6..10].try_into().expect("4 bytes"));
+    info.check_len(len)?;
+    Ok(len as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assoc_static;
+
+    struct Hello;
+    struct Bye;
+    assoc_static!(MessageTag:Hello, MessageInfo = MessageInfo {
+        magic: 0x1234,
+        id: 1,
+        min_len: 2,
+        max_len: 4,
+    });
+    assoc_static!(MessageTag:Bye, MessageInfo = MessageInfo {
+        magic: 0x1234,
+        id: 2,
+        min_len: 0,
+        max_len: 0,
+    });
+
+    #[test]
+    fn validation() {
+        let header = encode_header::<Hello>(3).unwrap();
+        assert_eq!(decode_header::<Hello>(&header), Ok(3));
+        assert_eq!(
+            decode_header::<Bye>(&header),
+            Err(HeaderError::MessageId(1))
+        );
+        assert_eq!(
+            decode_header::<Hello>(&header[..9]),
+            Err(HeaderError::Truncated)
+        );
+        assert_eq!(encode_header::<Hello>(1), Err(HeaderError::Length(1)));
+        let mut bad = header;
+        bad[0] = 0;
+        assert_eq!(
+            decode_header::<Hello>(&bad),
+            Err(HeaderError::Magic(0x1200))
+        );
+    }
+}
@@ -0,0 +1,104 @@
+crate::tags! {
+    /// Tag for the `ModelInfo` of a model wrapper type.
+    pub ModelTag: ModelInfo;
+}
+
+/// Metadata of a machine learning model.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModelInfo {
+    /// Name of the model
+    pub name: &'static str,
+    /// Shape of the input tensor
+    pub input_shape: &'static [usize],
+    /// Names of the output labels
+    pub labels: &'static [&'static str],
+    /// Per channel mean subtracted from the input
+    pub mean: &'static [f32],
+    /// Per channel standard deviation the input is divided by
+    pub std: &'static [f32],
+}
+
+impl ModelInfo {
+    /// Returns the number of elements of the input tensor.
+    pub fn input_len(&self) -> usize {
+        self.input_shape.iter().product()
+    }
+
+    /// Returns the name of the label at 'index'.
+    pub fn label(&self, index: usize) -> Option<&'static str> {
+        self.labels.get(index).copied()
+    }
+
+    /// Normalizes 'input' in place, the channel of an element is its index modulo the
+    /// number of channels in 'mean' and 'std'.
+    pub fn normalize(&self, input: &mut [f32]) {
+        let channels = self.mean.len().min(self.std.len());
+        if channels == 0 {
+            return;
+        }
+        for (i, value) in input.iter_mut().enumerate() {
+            let channel = i % channels;
+            *value = (*value - self.mean[channel]) / self.std[channel];
+        }
+    }
+}
+
+/// Declares a static listing the `ModelInfo` of the given model types, for serving layers
+/// that enumerate the models compiled into the binary.
+///
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Digits;
+/// struct Faces;
+/// assoc_static!(ModelTag:Digits, ModelInfo = ModelInfo {
+///     name: "digits",
+///     input_shape: &[28, 28],
+///     labels: &["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"],
+///     mean: &[0.13],
+///     std: &[0.31],
+/// });
+/// assoc_static!(ModelTag:Faces, ModelInfo = ModelInfo {
+///     name: "faces",
+///     input_shape: &[3, 64, 64],
+///     labels: &["face", "no face"],
+///     mean: &[0.5, 0.5, 0.5],
+///     std: &[0.25, 0.25, 0.25],
+/// });
+///
+/// model_registry!(static MODELS = [Digits, Faces]);
+///
+/// let names: Vec<_> = MODELS.iter().map(|model| model.name).collect();
+/// assert_eq!(names, ["digits", "faces"]);
+/// assert_eq!(MODELS[1].input_len(), 3 * 64 * 64);
+/// ```
+#[macro_export]
+macro_rules! model_registry {
+    ($(#[$meta:meta])* $vis:vis static $NAME:ident = [$($T:ty),* $(,)?]) => {
+        $(#[$meta])*
+        $vis static $NAME: &[&$crate::ModelInfo] = &[
+            $(<$T as $crate::AssocStaticRef<$crate::ModelInfo, $crate::ModelTag>>::STATIC_REF),*
+        ];
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ModelInfo;
+
+    #[test]
+    fn normalize() {
+        let info = ModelInfo {
+            name: "test",
+            input_shape: &[2, 2],
+            labels: &["a"],
+            mean: &[1.0, 2.0],
+            std: &[2.0, 4.0],
+        };
+        let mut input = [3.0, 6.0, 1.0, 2.0];
+        info.normalize(&mut input);
+        assert_eq!(input, [1.0, 1.0, 0.0, 0.0]);
+        assert_eq!(info.label(1), None);
+        assert_eq!(info.input_len(), 4);
+    }
+}
@@ -0,0 +1,42 @@
+/// Only a helper, needs to be public because of the macro.
+/// XORs 'bytes' with a key stream derived from 'key'. Applying it twice restores the input.
+#[doc(hidden)]
+pub const fn obfuscate<const N: usize>(bytes: &[u8], key: u64) -> [u8; N] {
+    let mut out = [0; N];
+    let mut i = 0;
+    while i < N {
+        out[i] = bytes[i] ^ key_byte(key, i);
+        i += 1;
+    }
+    out
+}
+
+/// Only a helper, needs to be public because of the macro.
+/// Decodes bytes encoded by `obfuscate()`.
+#[doc(hidden)]
+pub fn deobfuscate(bytes: &[u8], key: u64) -> Vec<u8> {
+    // keep the optimizer from folding the decoding into a plain text constant
+    let bytes = std::hint::black_box(bytes);
+    bytes
+        .iter()
+        .enumerate()
+        .map(|(i, byte)| byte ^ key_byte(key, i))
+        .collect()
+}
+
+const fn key_byte(key: u64, i: usize) -> u8 {
+    (key.rotate_left((i % 8) as u32 * 8) as u8) ^ (i as u8).wrapping_mul(0x9d)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{deobfuscate, obfuscate};
+
+    #[test]
+    fn roundtrip() {
+        const KEY: u64 = 0x0123_4567_89ab_cdef;
+        let encoded: [u8; 11] = obfuscate(b"hello world", KEY);
+        assert_ne!(&encoded, b"hello world");
+        assert_eq!(deobfuscate(&encoded, KEY), b"hello world");
+    }
+}
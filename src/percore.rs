@@ -0,0 +1,102 @@
+/// One value per core, selected by a core id function. Associated with `assoc_percore!()`.
+pub struct PerCore<T, const N: usize> {
+    values: [T; N],
+    core_id: fn() -> usize,
+}
+
+impl<T, const N: usize> PerCore<T, N> {
+    /// Creates the per core values, 'core_id' returns the id of the calling core.
+    pub const fn new(values: [T; N], core_id: fn() -> usize) -> Self {
+        PerCore { values, core_id }
+    }
+
+    /// Returns the value of the calling core.
+    ///
+    /// # Panics
+    ///
+    /// When the core id is out of range.
+    #[track_caller]
+    pub fn current(&self) -> &T {
+        let core = (self.core_id)();
+        match self.values.get(core) {
+            Some(value) => value,
+            None => panic!("core id {core} out of range, only {N} cores declared"),
+        }
+    }
+
+    /// Returns the value of 'core'.
+    pub fn get(&self, core: usize) -> Option<&T> {
+        self.values.get(core)
+    }
+
+    /// Returns the values of all cores.
+    pub fn all(&self) -> &[T; N] {
+        &self.values
+    }
+}
+
+/// Associates one value per core to a type, accessed through `PerCore::current()`. The
+/// values are given as an array and a function returning the id of the calling core has to
+/// be supplied since there is no portable way to obtain it.
+///
+/// ```
+/// use crate::assoc_static::*;
+/// use std::sync::atomic::{AtomicU32, Ordering};
+///
+/// fn core_id() -> usize {
+///     // on real hardware this reads the core id register
+///     0
+/// }
+///
+/// struct Uart;
+/// struct Irqs;
+/// assoc_percore!(Irqs:Uart, AtomicU32 = [AtomicU32::new(0), AtomicU32::new(0)]; core_id);
+///
+/// let irqs = <Uart as AssocStatic<PerCore<AtomicU32, 2>, Irqs>>::get_static();
+/// irqs.current().fetch_add(1, Ordering::Relaxed);
+/// assert_eq!(irqs.get(0).unwrap().load(Ordering::Relaxed), 1);
+/// ```
+#[macro_export]
+macro_rules! assoc_percore {
+    ($TAG:ty:$T:ty, $TARGET:ty = [$($INIT:expr),+ $(,)?]; $CORE_ID:expr) => {
+        $crate::assoc_static!(
+            $TAG:$T,
+            $crate::PerCore<$TARGET, { [$(stringify!($INIT)),+].len() }> =
+                $crate::PerCore::new([$($INIT),+], $CORE_ID)
+        );
+    };
+    ($T:ty, $TARGET:ty = [$($INIT:expr),+ $(,)?]; $CORE_ID:expr) => {
+        $crate::assoc_percore!(():$T, $TARGET = [$($INIT),+]; $CORE_ID);
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{AssocStatic, PerCore};
+
+    fn second_core() -> usize {
+        1
+    }
+
+    fn missing_core() -> usize {
+        3
+    }
+
+    struct TestType;
+    struct TestTag;
+    assoc_percore!(TestType, &'static str = ["core0", "core1"]; second_core);
+    assoc_percore!(TestTag:TestType, u8 = [0]; missing_core);
+
+    #[test]
+    fn current() {
+        let names = <TestType as AssocStatic<PerCore<&str, 2>>>::get_static();
+        assert_eq!(*names.current(), "core1");
+        assert_eq!(names.all(), &["core0", "core1"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "core id 3 out of range")]
+    fn out_of_range() {
+        <TestType as AssocStatic<PerCore<u8, 1>, TestTag>>::get_static().current();
+    }
+}
@@ -0,0 +1,145 @@
+use std::mem::{align_of, size_of};
+use std::ptr::NonNull;
+
+use crate::AssocStatic;
+
+crate::tags! {
+    /// Tag for the `Peripheral` description of a driver type.
+    pub PeripheralTag: Peripheral;
+}
+
+/// Describes the memory mapped register block of a peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Peripheral {
+    /// Name of the peripheral
+    pub name: &'static str,
+    /// Base address of the register block
+    pub base: usize,
+    /// Size of the register block in bytes
+    pub size: usize,
+}
+
+/// Returns a pointer to the register block of the peripheral associated with the driver
+/// type 'T'. No reference is handed out because the compiler may merge or elide plain reads
+/// through references while registers change behind its back. Access the registers with
+/// `read_volatile()` and `write_volatile()` through the pointer.
+///
+/// # Panics
+///
+/// When 'R' does not fit the register block or the base address is null or misaligned for
+/// 'R'.
+pub fn regs<T, R>() -> NonNull<R>
+where
+    T: AssocStatic<Peripheral, PeripheralTag> + ?Sized,
+{
+    let peripheral = T::get_static();
+    assert!(
+        size_of::<R>() <= peripheral.size,
+        "register block does not fit peripheral {}",
+        peripheral.name
+    );
+    assert!(
+        peripheral.base % align_of::<R>() == 0,
+        "misaligned base address of peripheral {}",
+        peripheral.name
+    );
+    NonNull::new(peripheral.base as *mut R)
+        .unwrap_or_else(|| panic!("null base address of peripheral {}", peripheral.name))
+}
+
+/// Associates the `Peripheral` at 'BASE' with a driver type and generates a
+/// `fn regs() -> NonNull<R>` accessor on the driver type. The size defaults to the size of
+/// the register block type 'R'.
+///
+/// ```no_run
+/// use crate::assoc_static::*;
+/// use std::ptr::addr_of;
+///
+/// #[repr(C)]
+/// struct UartRegisters {
+///     data: u32,
+///     status: u32,
+/// }
+///
+/// struct Uart0;
+/// assoc_peripheral!(Uart0, UartRegisters = 0x4000_1000);
+///
+/// let uart = Uart0::regs().as_ptr();
+/// // SAFETY: the target maps the UART at 0x4000_1000
+/// let status = unsafe { addr_of!((*uart).status).read_volatile() };
+/// ```
+#[macro_export]
+macro_rules! assoc_peripheral {
+    ($T:ty, $R:ty = $BASE:expr $(, size = $SIZE:expr)?) => {
+        $crate::assoc_static!(
+            $crate::PeripheralTag:$T,
+            $crate::Peripheral = $crate::Peripheral {
+                name: stringify!($T),
+                base: $BASE,
+                size: $crate::assoc_peripheral!(@size $R $(, $SIZE)?),
+            }
+        );
+
+        impl $T {
+            /// Returns a pointer to the register block of this peripheral, see `regs()`.
+            #[allow(dead_code)]
+            pub fn regs() -> std::ptr::NonNull<$R> {
+                $crate::regs::<$T, $R>()
+            }
+        }
+    };
+    (@size $R:ty) => {
+        std::mem::size_of::<$R>()
+    };
+    (@size $R:ty, $SIZE:expr) => {
+        $SIZE
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{regs, Peripheral};
+    use crate::{assoc_static, PeripheralTag};
+    use std::ptr::addr_of;
+
+    #[repr(C)]
+    struct Registers {
+        id: u32,
+        status: u32,
+    }
+
+    static MEMORY: Registers = Registers {
+        id: 0x55aa,
+        status: 1,
+    };
+
+    struct Fake;
+    assoc_static!(PeripheralTag:Fake, Peripheral = Peripheral {
+        name: "fake",
+        base: &MEMORY as *const Registers as usize,
+        size: 8,
+    }; boxed);
+
+    struct TooSmall;
+    assoc_static!(PeripheralTag:TooSmall, Peripheral = Peripheral {
+        name: "too small",
+        base: &MEMORY as *const Registers as usize,
+        size: 4,
+    }; boxed);
+
+    #[test]
+    fn registers() {
+        let registers = regs::<Fake, Registers>().as_ptr();
+        // SAFETY: the fake peripheral points to a static
+        unsafe {
+            assert_eq!(addr_of!((*registers).id).read_volatile(), 0x55aa);
+            assert_eq!(addr_of!((*registers).status).read_volatile(), 1);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "register block does not fit peripheral too small")]
+    fn too_small() {
+        regs::<TooSmall, Registers>();
+    }
+}
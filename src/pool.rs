@@ -0,0 +1,158 @@
+use std::fmt;
+use std::sync::OnceLock;
+
+crate::tags! {
+    /// Tag for the `Pool` work of a type is routed to.
+    pub PoolTag: Pool;
+}
+
+/// A job passed to an executor.
+pub type Job = Box<dyn FnOnce() + Send>;
+
+/// Executes jobs, implemented for closures taking a `Job`, so any thread pool can be bound
+/// with `pool.bind(|job| my_pool.spawn(job))`.
+pub trait Executor: Send + Sync {
+    /// Runs 'job', usually on another thread.
+    fn execute(&self, job: Job);
+}
+
+impl fmt::Debug for dyn Executor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Executor")
+    }
+}
+
+impl<F: Fn(Job) + Send + Sync> Executor for F {
+    fn execute(&self, job: Job) {
+        self(job)
+    }
+}
+
+/// A late bound executor associated with a workload type. The application binds the actual
+/// executor at startup, until then jobs run on a new thread each. Usually associated with
+/// `assoc_pool!()`.
+///
+/// ```
+/// use crate::assoc_static::*;
+/// use std::sync::mpsc;
+///
+/// struct Render;
+/// assoc_static!(PoolTag:Render, Pool = Pool::new());
+///
+/// PoolTag::of::<Render>()
+///     .bind(|job: Job| {
+///         std::thread::spawn(job);
+///     })
+///     .unwrap();
+///
+/// let (sender, receiver) = mpsc::channel();
+/// PoolTag::of::<Render>().spawn(move || sender.send(42).unwrap());
+/// assert_eq!(receiver.recv().unwrap(), 42);
+/// ```
+pub struct Pool {
+    executor: OnceLock<Box<dyn Executor>>,
+}
+
+impl Pool {
+    /// Creates an unbound pool.
+    pub const fn new() -> Self {
+        Pool {
+            executor: OnceLock::new(),
+        }
+    }
+
+    /// Binds the executor, fails when an executor was already bound.
+    pub fn bind(&self, executor: impl Executor + 'static) -> Result<(), Box<dyn Executor>> {
+        self.executor.set(Box::new(executor))
+    }
+
+    /// Returns true when an executor is bound.
+    pub fn is_bound(&self) -> bool {
+        self.executor.get().is_some()
+    }
+
+    /// Runs 'job' on the bound executor or on a new thread when none is bound.
+    pub fn spawn(&self, job: impl FnOnce() + Send + 'static) {
+        match self.executor.get() {
+            Some(executor) => executor.execute(Box::new(job)),
+            None => {
+                std::thread::spawn(job);
+            }
+        }
+    }
+}
+
+impl Default for Pool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for Pool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pool")
+            .field("bound", &self.is_bound())
+            .finish()
+    }
+}
+
+/// Associates an unbound `Pool` with a workload type and generates a `pool()` accessor on
+/// it.
+///
+/// ```
+/// use crate::assoc_static::*;
+/// use std::sync::mpsc;
+///
+/// struct Render;
+/// assoc_pool!(Render);
+///
+/// assert!(!Render::pool().is_bound());
+/// let (sender, receiver) = mpsc::channel();
+/// Render::pool().spawn(move || sender.send(42).unwrap());
+/// assert_eq!(receiver.recv().unwrap(), 42);
+/// ```
+#[macro_export]
+macro_rules! assoc_pool {
+    ($T:ty) => {
+        $crate::assoc_static!($crate::PoolTag:$T, $crate::Pool = $crate::Pool::new());
+
+        impl $T {
+            /// Returns the pool work of this type is routed to.
+            #[allow(dead_code)]
+            pub fn pool() -> &'static $crate::Pool {
+                $crate::PoolTag::of::<$T>()
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Job, Pool};
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::mpsc;
+
+    #[test]
+    fn unbound() {
+        let pool = Pool::new();
+        let (sender, receiver) = mpsc::channel();
+        pool.spawn(move || sender.send(1).unwrap());
+        assert_eq!(receiver.recv().unwrap(), 1);
+    }
+
+    #[test]
+    fn bound_inline() {
+        static RUNS: AtomicU32 = AtomicU32::new(0);
+        let pool = Pool::new();
+        pool.bind(|job: Job| {
+            RUNS.fetch_add(1, Ordering::Relaxed);
+            job()
+        })
+        .unwrap();
+        assert!(pool.bind(|job: Job| job()).is_err());
+        let (sender, receiver) = mpsc::channel();
+        pool.spawn(move || sender.send(2).unwrap());
+        assert_eq!(receiver.try_recv().unwrap(), 2);
+        assert_eq!(RUNS.load(Ordering::Relaxed), 1);
+    }
+}
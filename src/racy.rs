@@ -0,0 +1,79 @@
+use std::ptr;
+use std::sync::atomic::{AtomicPtr, Ordering};
+
+/// Only a helper, needs to be public because of the macro.
+///
+/// A lazily initialized heap object published through an atomic pointer, this avoids the
+/// state and waiting of a `OnceLock`. When threads race on the first access each of them
+/// builds a value, the first one stored wins and the others are dropped.
+#[doc(hidden)]
+pub struct RacyCell<T>(AtomicPtr<T>);
+
+// SAFETY: the value is only shared by reference after it was published, 'T: Send' because
+// it may be built on another thread than the one dropping a losing value.
+unsafe impl<T: Sync + Send> Sync for RacyCell<T> {}
+
+impl<T> RacyCell<T> {
+    pub const fn new() -> Self {
+        RacyCell(AtomicPtr::new(ptr::null_mut()))
+    }
+
+    pub fn get_or_init(&'static self, init: impl FnOnce() -> T) -> &'static T {
+        let mut value = self.0.load(Ordering::Acquire);
+        if value.is_null() {
+            let new = Box::into_raw(Box::new(init()));
+            value = match self.0.compare_exchange(
+                ptr::null_mut(),
+                new,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => new,
+                Err(winner) => {
+                    // SAFETY: 'new' was never published
+                    drop(unsafe { Box::from_raw(new) });
+                    winner
+                }
+            };
+        }
+        // SAFETY: published values are never freed or mutated
+        unsafe { &*value }
+    }
+}
+
+impl<T> Default for RacyCell<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::{assoc_static, AssocStatic};
+
+    static BUILT: AtomicUsize = AtomicUsize::new(0);
+
+    struct TestType;
+    assoc_static!(TestType, String = {
+        BUILT.fetch_add(1, Ordering::Relaxed);
+        "racy".to_string()
+    }; racy);
+
+    #[test]
+    fn first_writer_wins() {
+        let values: Vec<usize> = std::thread::scope(|scope| {
+            let threads: Vec<_> = (0..8)
+                .map(|_| scope.spawn(<TestType as AssocStatic<String>>::get_static))
+                .collect();
+            threads
+                .into_iter()
+                .map(|thread| thread.join().unwrap() as *const String as usize)
+                .collect()
+        });
+        assert!(values.iter().all(|&value| value == values[0]));
+        assert_eq!(TestType::get_static(), "racy");
+        assert!(BUILT.load(Ordering::Relaxed) >= 1);
+    }
+}
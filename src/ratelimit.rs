@@ -0,0 +1,134 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+crate::tags! {
+    /// Tag for the `RateLimiter` of a type.
+    pub RateLimitTag: RateLimiter;
+}
+
+/// A token bucket rate limiter to be associated with the message or request types it
+/// throttles, usually with `assoc_rate_limiter!()`.
+///
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Login;
+/// assoc_static!(RateLimitTag:Login, RateLimiter = RateLimiter::new(2, 1));
+///
+/// assert!(RateLimitTag::of::<Login>().check());
+/// assert!(RateLimitTag::of::<Login>().check());
+/// assert!(!RateLimitTag::of::<Login>().check());
+/// ```
+#[derive(Debug)]
+pub struct RateLimiter {
+    capacity: u32,
+    per_second: u32,
+    state: Mutex<Option<Bucket>>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    last: Instant,
+}
+
+impl RateLimiter {
+    /// Creates a limiter allowing bursts of 'capacity' and refilling 'per_second' tokens each
+    /// second. The bucket starts full.
+    pub const fn new(capacity: u32, per_second: u32) -> Self {
+        RateLimiter {
+            capacity,
+            per_second,
+            state: Mutex::new(None),
+        }
+    }
+
+    /// Takes one token, returns false when the rate is exceeded.
+    pub fn check(&self) -> bool {
+        self.check_at(Instant::now())
+    }
+
+    /// Returns how long to wait until the next token is available.
+    pub fn wait_time(&self) -> Duration {
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap_or_else(|err| err.into_inner());
+        let bucket = self.refill(&mut state, now);
+        if bucket.tokens >= 1.0 || self.per_second == 0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64((1.0 - bucket.tokens) / self.per_second as f64)
+        }
+    }
+
+    fn check_at(&self, now: Instant) -> bool {
+        let mut state = self.state.lock().unwrap_or_else(|err| err.into_inner());
+        let bucket = self.refill(&mut state, now);
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn refill<'a>(&self, state: &'a mut Option<Bucket>, now: Instant) -> &'a mut Bucket {
+        let bucket = state.get_or_insert(Bucket {
+            tokens: self.capacity as f64,
+            last: now,
+        });
+        let elapsed = now.saturating_duration_since(bucket.last).as_secs_f64();
+        bucket.tokens =
+            (bucket.tokens + elapsed * self.per_second as f64).min(self.capacity as f64);
+        bucket.last = now;
+        bucket
+    }
+}
+
+/// Associates a `RateLimiter` allowing bursts of 'CAPACITY' and refilling 'PER_SECOND'
+/// tokens each second with a type and generates a `limiter()` accessor on it.
+///
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Login;
+/// assoc_rate_limiter!(Login, 1, 1);
+///
+/// assert!(Login::limiter().check());
+/// assert!(!Login::limiter().check());
+/// ```
+#[macro_export]
+macro_rules! assoc_rate_limiter {
+    ($T:ty, $CAPACITY:expr, $PER_SECOND:expr) => {
+        $crate::assoc_static!(
+            $crate::RateLimitTag:$T,
+            $crate::RateLimiter = $crate::RateLimiter::new($CAPACITY, $PER_SECOND)
+        );
+
+        impl $T {
+            /// Returns the rate limiter throttling this type.
+            #[allow(dead_code)]
+            pub fn limiter() -> &'static $crate::RateLimiter {
+                $crate::RateLimitTag::of::<$T>()
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RateLimiter;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn refill() {
+        let limiter = RateLimiter::new(1, 10);
+        let start = Instant::now();
+        assert!(limiter.check_at(start));
+        assert!(!limiter.check_at(start));
+        assert!(!limiter.check_at(start + Duration::from_millis(50)));
+        assert!(limiter.check_at(start + Duration::from_millis(150)));
+        // never exceeds the capacity
+        assert!(limiter.check_at(start + Duration::from_secs(10)));
+        assert!(!limiter.check_at(start + Duration::from_secs(10)));
+    }
+}
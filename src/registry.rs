@@ -0,0 +1,294 @@
+use std::any::{Any, TypeId};
+use std::fmt;
+use std::marker::PhantomData;
+
+use crate::{Descriptor, Tag};
+
+/// An association listed in a `Registry`.
+#[derive(Clone, Copy)]
+pub struct Entry {
+    /// Returns the `type_name` of the type the static object is associated to
+    pub type_name: fn() -> &'static str,
+    /// Returns the `Tag::NAME` of the tag, or its `type_name` when it does not implement `Tag`
+    pub tag_name: fn() -> &'static str,
+    /// Returns the `TypeId` of the type the static object is associated to
+    pub type_id: fn() -> TypeId,
+    /// Returns the `TypeId` of the tag
+    pub tag_id: fn() -> TypeId,
+    /// Returns the associated static object
+    pub get: fn() -> &'static dyn Any,
+    /// Returns the descriptor of the association
+    pub descriptor: fn() -> Descriptor,
+}
+
+impl fmt::Debug for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Entry")
+            .field("type_name", &(self.type_name)())
+            .field("tag_name", &(self.tag_name)())
+            .finish()
+    }
+}
+
+/// Only a helper, needs to be public because of the macro. `TagName::<T>::NAME` is the
+/// `Tag::NAME` of 'T' and falls back to `NoTagName::NAME` for tags not implementing `Tag`.
+#[doc(hidden)]
+pub struct TagName<T: ?Sized>(PhantomData<T>);
+
+impl<T: Tag + ?Sized> TagName<T> {
+    pub const NAME: Option<&'static str> = Some(T::NAME);
+}
+
+#[doc(hidden)]
+pub trait NoTagName {
+    const NAME: Option<&'static str> = None;
+}
+
+impl<T: ?Sized> NoTagName for TagName<T> {}
+
+/// Associations resolvable by the names of their type and tag, declared with
+/// `assoc_registry!()`. Types are named by their full `type_name`. Tags are named by their
+/// `Tag::NAME`, as declared with `tags!()`, or their `type_name`. The tag name can also be
+/// given unqualified, without the module path, when no other tag of the type has the same
+/// unqualified name. The tag of untagged associations is `()`.
+///
+/// The entries are fixed where the registry is declared, associations are never registered
+/// at runtime. A plugin host declares one registry per plugin and looks into the registries
+/// of the plugins it has loaded. Being immutable from the start, lookups never take a lock
+/// and there is no startup phase to seal.
+///
+/// ```
+/// use crate::assoc_static::*;
+///
+/// mod tags {
+///     assoc_static::tags! {
+///         pub Hello;
+///     }
+/// }
+///
+/// struct Example;
+/// assoc_static!(Example, u32 = 42);
+/// assoc_static!(tags::Hello:Example, &'static str = "Hello World!");
+///
+/// assoc_registry!(static REGISTRY = [
+///     ():Example => u32,
+///     tags::Hello:Example => &'static str,
+/// ]);
+///
+/// let example = std::any::type_name::<Example>();
+/// let greeting = REGISTRY.lookup(example, "Hello").unwrap();
+/// assert_eq!(greeting.downcast_ref::<&str>(), Some(&"Hello World!"));
+/// assert!(REGISTRY.lookup(example, <tags::Hello as Tag>::NAME).is_some());
+/// assert_eq!(REGISTRY.lookup(example, "()").unwrap().downcast_ref::<u32>(), Some(&42));
+/// assert!(REGISTRY.lookup("Example", "Hello").is_none());
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Registry(pub &'static [Entry]);
+
+impl Registry {
+    /// Returns the first association to the type named 'type_name' with the tag named
+    /// 'tag_name'. An unqualified tag name that matches several different tags is ambiguous
+    /// and resolves to `None`.
+    pub fn lookup(&self, type_name: &str, tag_name: &str) -> Option<&'static dyn Any> {
+        let entries = self
+            .0
+            .iter()
+            .filter(|entry| (entry.type_name)() == type_name);
+        if let Some(entry) = entries.clone().find(|entry| (entry.tag_name)() == tag_name) {
+            return Some((entry.get)());
+        }
+        let mut unqualified =
+            entries.filter(|entry| (entry.tag_name)().rsplit("::").next() == Some(tag_name));
+        let first = unqualified.next()?;
+        if unqualified.any(|entry| (entry.tag_id)() != (first.tag_id)()) {
+            return None;
+        }
+        Some((first.get)())
+    }
+
+    /// Returns the 'TARGET' associated with 'TAG' to the type of 'value', `None` when the
+    /// registry does not list such an association. This lets generic code handle types that
+    /// were never registered.
+    ///
+    /// ```
+    /// use crate::assoc_static::*;
+    /// use std::any::Any;
+    ///
+    /// struct Example;
+    /// struct Unlisted;
+    /// assoc_static!(Example, u32 = 42);
+    ///
+    /// assoc_registry!(static REGISTRY = [():Example => u32]);
+    ///
+    /// let values: [&dyn Any; 2] = [&Example, &Unlisted];
+    /// assert_eq!(REGISTRY.try_assoc::<u32, ()>(values[0]), Some(&42));
+    /// assert_eq!(REGISTRY.try_assoc::<u32, ()>(values[1]), None);
+    /// ```
+    pub fn try_assoc<TARGET: 'static, TAG: 'static>(
+        &self,
+        value: &dyn Any,
+    ) -> Option<&'static TARGET> {
+        self.try_assoc_by_id::<TARGET, TAG>(value.type_id())
+    }
+
+    /// Returns the 'TARGET' associated with 'TAG' to the type with the `TypeId` 'type_id',
+    /// `None` when the registry does not list such an association.
+    pub fn try_assoc_by_id<TARGET: 'static, TAG: 'static>(
+        &self,
+        type_id: TypeId,
+    ) -> Option<&'static TARGET> {
+        self.0
+            .iter()
+            .filter(|entry| (entry.type_id)() == type_id && (entry.tag_id)() == TypeId::of::<TAG>())
+            .find_map(|entry| (entry.get)().downcast_ref())
+    }
+
+    /// Like `try_assoc()` but panics with 'message' when the registry does not list the
+    /// association. The panic is reported at the caller.
+    ///
+    /// ```should_panic
+    /// use crate::assoc_static::*;
+    ///
+    /// struct Unlisted;
+    /// assoc_registry!(static REGISTRY = []);
+    ///
+    /// REGISTRY.expect_static::<u32, ()>(&Unlisted, "Unlisted was not registered");
+    /// ```
+    #[track_caller]
+    pub fn expect_static<TARGET: 'static, TAG: 'static>(
+        &self,
+        value: &dyn Any,
+        message: &str,
+    ) -> &'static TARGET {
+        self.try_assoc::<TARGET, TAG>(value).expect(message)
+    }
+
+    /// Returns the descriptors of all listed associations, this tells debuggers and leak
+    /// reports what is attached to the listed types.
+    ///
+    /// ```
+    /// use crate::assoc_static::*;
+    ///
+    /// struct Example;
+    /// assoc_static!(Example, u32 = 42);
+    /// assoc_static!(Example, [u8; 64] = [0; 64]);
+    ///
+    /// assoc_registry!(static REGISTRY = [():Example => u32, ():Example => [u8; 64]]);
+    ///
+    /// let size: usize = REGISTRY.descriptors().map(|descriptor| descriptor.size).sum();
+    /// assert_eq!(size, 68);
+    /// for descriptor in REGISTRY.descriptors() {
+    ///     println!("{} at {}", descriptor.target_name, descriptor.location.unwrap());
+    /// }
+    /// ```
+    pub fn descriptors(&self) -> impl Iterator<Item = Descriptor> + '_ {
+        self.0.iter().map(|entry| (entry.descriptor)())
+    }
+}
+
+/// Declares a static `Registry` listing associations as 'TAG:T => TARGET', untagged
+/// associations are listed with the '()' tag.
+#[macro_export]
+macro_rules! assoc_registry {
+    (
+        $(#[$meta:meta])*
+        $vis:vis static $NAME:ident = [$($TAG:ty:$T:ty => $TARGET:ty),* $(,)?]
+    ) => {
+        $(#[$meta])*
+        $vis static $NAME: $crate::Registry = $crate::Registry(&[
+            $($crate::registry::Entry {
+                type_name: std::any::type_name::<$T>,
+                tag_name: || {
+                    // only used by tags without a 'Tag' impl
+                    #[allow(unused_imports)]
+                    use $crate::registry::NoTagName as _;
+                    match $crate::registry::TagName::<$TAG>::NAME {
+                        Some(name) => name,
+                        None => std::any::type_name::<$TAG>(),
+                    }
+                },
+                type_id: std::any::TypeId::of::<$T>,
+                tag_id: std::any::TypeId::of::<$TAG>,
+                get: || <$T as $crate::AssocStatic<$TARGET, $TAG>>::get_static(),
+                descriptor: <$T as $crate::AssocStatic<$TARGET, $TAG>>::descriptor,
+            }),*
+        ]);
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{assoc_static, AssocStatic};
+
+    struct TestType;
+    struct TestTag;
+    assoc_static!(TestType, u32 = 199);
+    assoc_static!(TestTag:TestType, u32 = 991; boxed);
+
+    assoc_registry!(static REGISTRY = [():TestType => u32, TestTag:TestType => u32]);
+
+    #[test]
+    fn lookup() {
+        let name = std::any::type_name::<TestType>();
+        let tagged = REGISTRY
+            .lookup(name, std::any::type_name::<TestTag>())
+            .unwrap();
+        assert_eq!(tagged.downcast_ref::<u32>(), Some(&991));
+        // 'TestTag' does not implement 'Tag', its 'type_name' and unqualified name are used
+        assert!(REGISTRY.lookup(name, "TestTag").is_some());
+        assert!(std::ptr::eq(
+            REGISTRY
+                .lookup(name, "()")
+                .unwrap()
+                .downcast_ref::<u32>()
+                .unwrap(),
+            <TestType as AssocStatic<u32>>::get_static(),
+        ));
+        assert_eq!(REGISTRY.descriptors().count(), 2);
+    }
+
+    mod first {
+        crate::tags! {
+            pub Shared;
+        }
+    }
+
+    mod second {
+        crate::tags! {
+            pub Shared;
+        }
+    }
+
+    assoc_static!(first::Shared:TestType, u32 = 1);
+    assoc_static!(second::Shared:TestType, u32 = 2);
+
+    assoc_registry!(static AMBIGUOUS = [
+        first::Shared:TestType => u32,
+        second::Shared:TestType => u32,
+    ]);
+
+    #[test]
+    fn ambiguous_unqualified_tag() {
+        use crate::Tag;
+        let name = std::any::type_name::<TestType>();
+        assert!(AMBIGUOUS.lookup(name, "Shared").is_none());
+        let second = AMBIGUOUS.lookup(name, second::Shared::NAME).unwrap();
+        assert_eq!(second.downcast_ref::<u32>(), Some(&2));
+    }
+
+    #[test]
+    fn try_assoc() {
+        assert_eq!(REGISTRY.try_assoc::<u32, ()>(&TestType), Some(&199));
+        assert_eq!(REGISTRY.try_assoc::<u32, TestTag>(&TestType), Some(&991));
+        assert_eq!(REGISTRY.try_assoc::<u16, TestTag>(&TestType), None);
+        assert_eq!(REGISTRY.try_assoc::<u32, ()>(&TestTag), None);
+        let id = std::any::TypeId::of::<TestType>();
+        assert_eq!(REGISTRY.try_assoc_by_id::<u32, ()>(id), Some(&199));
+    }
+
+    #[test]
+    #[should_panic(expected = "no u16 for TestType")]
+    fn expect_static() {
+        REGISTRY.expect_static::<u16, ()>(&TestType, "no u16 for TestType");
+    }
+}
@@ -0,0 +1,85 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::AssocStatic;
+
+crate::tags! {
+    /// Tag for the `Sampler` of an event type.
+    pub SamplingTag: Sampler;
+}
+
+/// Samples one of every 'N' events, 'N' can be changed at runtime. Zero disables sampling,
+/// one samples every event.
+#[derive(Debug)]
+pub struct Sampler {
+    every: AtomicU64,
+    seen: AtomicU64,
+}
+
+impl Sampler {
+    /// Creates a sampler sampling one of every 'every' events.
+    pub const fn every(every: u64) -> Self {
+        Sampler {
+            every: AtomicU64::new(every),
+            seen: AtomicU64::new(0),
+        }
+    }
+
+    /// Changes the sampling rate.
+    pub fn set_every(&self, every: u64) {
+        self.every.store(every, Ordering::Relaxed);
+    }
+
+    /// Returns the current sampling rate.
+    pub fn rate(&self) -> u64 {
+        self.every.load(Ordering::Relaxed)
+    }
+
+    /// Accounts an event and returns true when it shall be sampled.
+    pub fn sample(&self) -> bool {
+        match self.every.load(Ordering::Relaxed) {
+            0 => false,
+            1 => true,
+            every => self.seen.fetch_add(1, Ordering::Relaxed) % every == 0,
+        }
+    }
+}
+
+/// Decides whether an event is sampled by the `Sampler` associated with its type.
+///
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Request;
+/// assoc_static!(SamplingTag:Request, Sampler = Sampler::every(2));
+///
+/// let sampled = (0..10).filter(|_| Request.should_sample()).count();
+/// assert_eq!(sampled, 5);
+///
+/// SamplingTag::of::<Request>().set_every(0);
+/// assert!(!Request.should_sample());
+/// ```
+pub trait ShouldSample {
+    /// Returns true when this event shall be sampled.
+    fn should_sample(&self) -> bool;
+}
+
+impl<T: AssocStatic<Sampler, SamplingTag> + ?Sized> ShouldSample for T {
+    fn should_sample(&self) -> bool {
+        T::get_static().sample()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Sampler;
+
+    #[test]
+    fn rates() {
+        let sampler = Sampler::every(3);
+        let sampled: Vec<_> = (0..6).map(|_| sampler.sample()).collect();
+        assert_eq!(sampled, [true, false, false, true, false, false]);
+        sampler.set_every(1);
+        assert!(sampler.sample());
+        assert_eq!(sampler.rate(), 1);
+    }
+}
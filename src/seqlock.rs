@@ -0,0 +1,166 @@
+use std::cell::UnsafeCell;
+use std::hint;
+use std::mem::{size_of, MaybeUninit};
+use std::sync::atomic::{self, AtomicU8, AtomicUsize, Ordering};
+
+/// Plain data that can be copied byte by byte through a `SeqLock`.
+///
+/// # Safety
+///
+/// Every bit pattern of `size_of::<Self>()` bytes must be a valid value of the type. Thus it
+/// must not have padding bytes, references or types with invalid bit patterns such as
+/// `bool`, `char` or enums.
+pub unsafe trait Pod: Copy + 'static {}
+
+macro_rules! impl_pod {
+    ($($T:ty),*) => {
+        // SAFETY: primitive numbers are valid for every bit pattern
+        $(unsafe impl Pod for $T {})*
+    };
+}
+
+impl_pod!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+// SAFETY: arrays have no padding between their elements
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {}
+
+/// A sequence lock for small plain data values which are read often and written rarely, such
+/// as timestamps or sampling rates. Readers never block writers and take no lock, they retry
+/// when a write happened while they were copying the value. Writers exclude each other by
+/// spinning.
+///
+/// The value is copied through atomic bytes, thus a racing read is no data race and the
+/// `Pod` bound guarantees that a torn copy is still a valid value, it is discarded anyway.
+///
+/// ```
+/// use crate::assoc_static::*;
+///
+/// #[repr(C)]
+/// #[derive(Clone, Copy, Debug, PartialEq)]
+/// struct Window {
+///     start: u64,
+///     end: u64,
+/// }
+///
+/// // SAFETY: 'repr(C)' with two 'u64' fields has no padding
+/// unsafe impl Pod for Window {}
+///
+/// struct Metrics;
+/// assoc_static!(Metrics, SeqLock<Window> = SeqLock::new(Window { start: 0, end: 10 }));
+///
+/// let window = <Metrics as AssocStatic<SeqLock<Window>>>::get_static();
+/// window.write(Window { start: 10, end: 20 });
+/// assert_eq!(window.read(), Window { start: 10, end: 20 });
+/// ```
+pub struct SeqLock<T> {
+    sequence: AtomicUsize,
+    value: UnsafeCell<T>,
+}
+
+// SAFETY: the value is only accessed through atomic bytes, readers discard copies that raced
+// with a write, writers are serialized by the odd sequence number.
+unsafe impl<T: Pod + Send> Sync for SeqLock<T> {}
+
+impl<T: Pod> SeqLock<T> {
+    /// Creates a sequence lock holding 'value'.
+    pub const fn new(value: T) -> Self {
+        SeqLock {
+            sequence: AtomicUsize::new(0),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    /// Returns a copy of the value.
+    pub fn read(&self) -> T {
+        loop {
+            let before = self.sequence.load(Ordering::Acquire);
+            if before & 1 == 0 {
+                let mut value = MaybeUninit::<T>::uninit();
+                let target = value.as_mut_ptr().cast::<u8>();
+                for offset in 0..size_of::<T>() {
+                    // SAFETY: 'offset' is within 'value'
+                    unsafe {
+                        target
+                            .add(offset)
+                            .write(self.byte(offset).load(Ordering::Relaxed))
+                    };
+                }
+                atomic::fence(Ordering::Acquire);
+                if self.sequence.load(Ordering::Relaxed) == before {
+                    // SAFETY: all bytes are initialized and 'T: Pod' is valid for any of them
+                    return unsafe { value.assume_init() };
+                }
+            }
+            hint::spin_loop();
+        }
+    }
+
+    /// Replaces the value.
+    pub fn write(&self, value: T) {
+        let mut sequence = self.sequence.load(Ordering::Relaxed);
+        loop {
+            if sequence & 1 == 0 {
+                match self.sequence.compare_exchange_weak(
+                    sequence,
+                    sequence.wrapping_add(1),
+                    Ordering::Acquire,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => break,
+                    Err(current) => sequence = current,
+                }
+            } else {
+                hint::spin_loop();
+                sequence = self.sequence.load(Ordering::Relaxed);
+            }
+        }
+        atomic::fence(Ordering::Release);
+        let source = (&value as *const T).cast::<u8>();
+        for offset in 0..size_of::<T>() {
+            // SAFETY: 'offset' is within 'value' which has no padding because 'T: Pod'
+            self.byte(offset)
+                .store(unsafe { source.add(offset).read() }, Ordering::Relaxed);
+        }
+        self.sequence
+            .store(sequence.wrapping_add(2), Ordering::Release);
+    }
+
+    fn byte(&self, offset: usize) -> &AtomicU8 {
+        debug_assert!(offset < size_of::<T>());
+        // SAFETY: 'offset' is within the value, once shared it is only accessed through these
+        // atomic bytes. 'AtomicU8' has the size and alignment of 'u8'.
+        unsafe { AtomicU8::from_ptr(self.value.get().cast::<u8>().add(offset)) }
+    }
+}
+
+impl<T: Pod + std::fmt::Debug> std::fmt::Debug for SeqLock<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("SeqLock").field(&self.read()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SeqLock;
+
+    #[test]
+    fn no_torn_reads() {
+        static PAIR: SeqLock<[u64; 2]> = SeqLock::new([0, 0]);
+        std::thread::scope(|scope| {
+            scope.spawn(|| {
+                for n in 1..=10_000 {
+                    PAIR.write([n, n]);
+                }
+            });
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    for _ in 0..10_000 {
+                        let [a, b] = PAIR.read();
+                        assert_eq!(a, b);
+                    }
+                });
+            }
+        });
+        assert_eq!(PAIR.read(), [10_000, 10_000]);
+    }
+}
@@ -0,0 +1,259 @@
+use std::any::Any;
+use std::fmt;
+use std::sync::{Mutex, OnceLock, PoisonError, RwLock};
+
+use crate::{Pod, SeqLock, Watched};
+
+type State = Box<dyn Any + Send + Sync>;
+
+/// Mutable static objects whose state can be copied out and written back by `Snapshots`.
+pub trait Restorable: Sync {
+    /// Returns a copy of the current state.
+    fn snapshot(&self) -> State;
+
+    /// Writes back a state returned by `snapshot()` of the same object.
+    ///
+    /// # Panics
+    ///
+    /// When 'state' was taken from an object of another type.
+    fn restore(&self, state: &(dyn Any + Send + Sync));
+}
+
+fn downcast<T: 'static>(state: &(dyn Any + Send + Sync)) -> &T {
+    state
+        .downcast_ref()
+        .expect("state restored to an object of another type")
+}
+
+impl<T: Clone + Send + Sync + 'static> Restorable for Mutex<T> {
+    fn snapshot(&self) -> State {
+        Box::new(self.lock().unwrap_or_else(PoisonError::into_inner).clone())
+    }
+
+    fn restore(&self, state: &(dyn Any + Send + Sync)) {
+        *self.lock().unwrap_or_else(PoisonError::into_inner) = downcast::<T>(state).clone();
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> Restorable for RwLock<T> {
+    fn snapshot(&self) -> State {
+        Box::new(self.read().unwrap_or_else(PoisonError::into_inner).clone())
+    }
+
+    fn restore(&self, state: &(dyn Any + Send + Sync)) {
+        *self.write().unwrap_or_else(PoisonError::into_inner) = downcast::<T>(state).clone();
+    }
+}
+
+/// Restoring notifies the subscribers like any other write.
+impl<T: Clone + Send + Sync + 'static> Restorable for Watched<T> {
+    fn snapshot(&self) -> State {
+        Box::new(self.read().clone())
+    }
+
+    fn restore(&self, state: &(dyn Any + Send + Sync)) {
+        self.set(downcast::<T>(state).clone());
+    }
+}
+
+impl<T: Pod + Send + Sync> Restorable for SeqLock<T> {
+    fn snapshot(&self) -> State {
+        Box::new(self.read())
+    }
+
+    fn restore(&self, state: &(dyn Any + Send + Sync)) {
+        self.write(*downcast::<T>(state));
+    }
+}
+
+/// The states of all objects of a `Snapshots` set, taken by `Snapshots::snapshot_all()`.
+pub struct Snapshot(Vec<State>);
+
+impl fmt::Debug for Snapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Snapshot")
+            .field("objects", &self.0.len())
+            .finish()
+    }
+}
+
+/// A set of mutable associations whose state can be captured and rolled back together, for
+/// integration tests and crash recovery. Declared with `assoc_snapshots!()` listing the
+/// associations as 'TAG:T => TARGET', where 'TARGET' implements `Restorable`.
+///
+/// ```
+/// use crate::assoc_static::*;
+/// use std::sync::Mutex;
+///
+/// struct Config;
+/// assoc_static!(Config, u32 = 8080; sync(mutex));
+/// assoc_static!(Config, Watched<&'static str> = Watched::new("localhost"));
+///
+/// assoc_snapshots!(static STATE = [
+///     ():Config => Mutex<u32>,
+///     ():Config => Watched<&'static str>,
+/// ]);
+///
+/// let snapshot = STATE.snapshot_all();
+/// *<Config as AssocMutex<u32>>::lock_static() = 8443;
+/// <Config as AssocStatic<Watched<&str>>>::get_static().set("example.com");
+///
+/// STATE.restore(&snapshot);
+/// assert_eq!(*<Config as AssocMutex<u32>>::lock_static(), 8080);
+/// assert_eq!(*<Config as AssocStatic<Watched<&str>>>::get_static().read(), "localhost");
+/// ```
+pub struct Snapshots {
+    objects: &'static [fn() -> &'static dyn Restorable],
+    isolated: Mutex<()>,
+    baseline: OnceLock<Snapshot>,
+}
+
+impl Snapshots {
+    /// Creates a set of the objects returned by 'objects'.
+    pub const fn new(objects: &'static [fn() -> &'static dyn Restorable]) -> Self {
+        Snapshots {
+            objects,
+            isolated: Mutex::new(()),
+            baseline: OnceLock::new(),
+        }
+    }
+
+    /// Captures the state of all objects in the set.
+    pub fn snapshot_all(&self) -> Snapshot {
+        Snapshot(
+            self.objects
+                .iter()
+                .map(|object| object().snapshot())
+                .collect(),
+        )
+    }
+
+    /// Writes back the states captured by `snapshot_all()` of this set.
+    ///
+    /// # Panics
+    ///
+    /// When 'snapshot' was taken from another set.
+    pub fn restore(&self, snapshot: &Snapshot) {
+        assert_eq!(
+            self.objects.len(),
+            snapshot.0.len(),
+            "snapshot of another set restored"
+        );
+        self.objects
+            .iter()
+            .zip(&snapshot.0)
+            .for_each(|(object, state)| object().restore(state.as_ref()));
+    }
+
+    /// Runs 'test' with all objects of the set reset to the state they had at the first call
+    /// and restores their previous state afterwards, also when 'test' panics. Calls on the
+    /// same set run one after another, thus concurrently running tests do not see each
+    /// other's writes. This is what `#[assoc_test]` expands to.
+    pub fn isolate<R>(&self, test: impl FnOnce() -> R) -> R {
+        let _isolated = self.isolated.lock().unwrap_or_else(PoisonError::into_inner);
+        let baseline = self.baseline.get_or_init(|| self.snapshot_all());
+        let _restore = Restore(self, self.snapshot_all());
+        self.restore(baseline);
+        test()
+    }
+}
+
+struct Restore<'a>(&'a Snapshots, Snapshot);
+
+impl Drop for Restore<'_> {
+    fn drop(&mut self) {
+        self.0.restore(&self.1);
+    }
+}
+
+impl fmt::Debug for Snapshots {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Snapshots")
+            .field("objects", &self.objects.len())
+            .finish()
+    }
+}
+
+/// Declares a static `Snapshots` set listing associations as 'TAG:T => TARGET', untagged
+/// associations are listed with the '()' tag.
+#[macro_export]
+macro_rules! assoc_snapshots {
+    (
+        $(#[$meta:meta])*
+        $vis:vis static $NAME:ident = [$($TAG:ty:$T:ty => $TARGET:ty),* $(,)?]
+    ) => {
+        $(#[$meta])*
+        $vis static $NAME: $crate::Snapshots = $crate::Snapshots::new(&[
+            $(|| <$T as $crate::AssocStatic<$TARGET, $TAG>>::get_static()),*
+        ]);
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Mutex, RwLock};
+
+    use crate::{assoc_static, AssocStatic, SeqLock};
+
+    struct TestType;
+    assoc_static!(TestType, Vec<u32> = Vec::new(); sync(mutex));
+    assoc_static!(TestType, u32 = 1; sync(rwlock));
+    assoc_static!(TestType, SeqLock<u64> = SeqLock::new(2));
+
+    assoc_snapshots!(static STATE = [
+        ():TestType => Mutex<Vec<u32>>,
+        ():TestType => RwLock<u32>,
+        ():TestType => SeqLock<u64>,
+    ]);
+
+    assoc_snapshots!(static EMPTY = []);
+
+    fn mutex() -> &'static Mutex<Vec<u32>> {
+        <TestType as AssocStatic<Mutex<Vec<u32>>>>::get_static()
+    }
+
+    #[test]
+    fn roll_back() {
+        let snapshot = STATE.snapshot_all();
+        mutex().lock().unwrap().push(3);
+        *<TestType as AssocStatic<RwLock<u32>>>::get_static()
+            .write()
+            .unwrap() = 4;
+        <TestType as AssocStatic<SeqLock<u64>>>::get_static().write(5);
+
+        STATE.restore(&snapshot);
+        assert!(mutex().lock().unwrap().is_empty());
+        assert_eq!(
+            *<TestType as AssocStatic<RwLock<u32>>>::get_static()
+                .read()
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            <TestType as AssocStatic<SeqLock<u64>>>::get_static().read(),
+            2
+        );
+    }
+
+    struct Isolated;
+    assoc_static!(Isolated, SeqLock<u64> = SeqLock::new(7));
+    assoc_snapshots!(static ISOLATED = [():Isolated => SeqLock<u64>]);
+
+    #[test]
+    fn isolate() {
+        let seqlock = <Isolated as AssocStatic<SeqLock<u64>>>::get_static();
+        let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            ISOLATED.isolate(|| {
+                seqlock.write(6);
+                panic!("failing test");
+            })
+        }));
+        assert_eq!(seqlock.read(), 7);
+    }
+
+    #[test]
+    #[should_panic(expected = "snapshot of another set restored")]
+    fn other_set() {
+        STATE.restore(&EMPTY.snapshot_all());
+    }
+}
@@ -0,0 +1,82 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, PoisonError};
+
+use crate::Descriptor;
+
+type Entry = (fn() -> Descriptor, &'static Counter);
+
+static COUNTERS: Mutex<Vec<Entry>> = Mutex::new(Vec::new());
+
+/// Only a helper, needs to be public because of the macro
+#[doc(hidden)]
+pub struct Counter(AtomicUsize);
+
+impl Counter {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        Counter(AtomicUsize::new(0))
+    }
+
+    /// Counts an access, the first access registers the counter for `report()`.
+    pub fn hit(&'static self, descriptor: fn() -> Descriptor) {
+        if self.0.fetch_add(1, Ordering::Relaxed) == 0 {
+            COUNTERS
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push((descriptor, self));
+        }
+    }
+}
+
+/// Returns all associations that have been accessed through `get_static()` so far together
+/// with the number of accesses, the most accessed first. Associations that have never been
+/// accessed are not listed. Accesses through `AssocStaticRef::STATIC_REF` are not counted.
+///
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Example;
+/// assoc_static!(Example, u32 = 42);
+///
+/// for _ in 0..3 {
+///     Example::get_static();
+/// }
+///
+/// let report = stats::report();
+/// assert_eq!(report[0].0, <Example as AssocStatic<u32>>::descriptor());
+/// assert_eq!(report[0].1, 3);
+/// ```
+pub fn report() -> Vec<(Descriptor, usize)> {
+    let mut report: Vec<_> = COUNTERS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .iter()
+        .map(|(descriptor, counter)| (descriptor(), counter.0.load(Ordering::Relaxed)))
+        .collect();
+    report.sort_by_key(|(_, hits)| std::cmp::Reverse(*hits));
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{assoc_static, stats, AssocStatic};
+
+    struct TestType;
+    assoc_static!(TestType, [u8, u16] = (1, 2));
+
+    #[test]
+    fn counts() {
+        let count = |descriptor| {
+            stats::report()
+                .into_iter()
+                .find(|(d, _)| *d == descriptor)
+                .map(|(_, hits)| hits)
+        };
+        let descriptor = <TestType as AssocStatic<u8>>::descriptor();
+        assert_eq!(count(descriptor), None);
+        <TestType as AssocStatic<u8>>::get_static();
+        <TestType as AssocStatic<u8>>::get_static();
+        assert_eq!(count(descriptor), Some(2));
+        assert_eq!(count(<TestType as AssocStatic<u16>>::descriptor()), None);
+    }
+}
@@ -0,0 +1,109 @@
+use crate::AssocStatic;
+
+crate::tags! {
+    /// Tag for the keyword table of a token type, associated with `assoc_keywords!()`.
+    pub KeywordsTag;
+    /// Tag for the operator precedence table of a token type, associated with
+    /// `assoc_precedence!()`.
+    pub PrecedenceTag;
+}
+
+/// A static key/value table. Lookups scan the table, which is the fastest for the small
+/// tables found in lexers and parsers.
+#[derive(Debug)]
+pub struct Table<K: 'static, V: 'static>(pub &'static [(K, V)]);
+
+impl<K, V> Table<K, V> {
+    /// Returns the value of 'key'.
+    pub fn get<Q>(&self, key: &Q) -> Option<&'static V>
+    where
+        K: PartialEq<Q>,
+        Q: ?Sized,
+    {
+        self.0
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value)
+    }
+}
+
+/// Returns the token of the keyword 'word' from the keyword table of 'T'.
+///
+/// ```
+/// use crate::assoc_static::*;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Token {
+///     If,
+///     Else,
+///     Plus,
+///     Star,
+/// }
+///
+/// assoc_keywords!(Token { "if" => Token::If, "else" => Token::Else });
+/// assoc_precedence!(Token { Token::Plus => 1, Token::Star => 2 });
+///
+/// assert_eq!(keyword::<Token>("else"), Some(&Token::Else));
+/// assert_eq!(keyword::<Token>("then"), None);
+/// assert!(precedence(&Token::Star) > precedence(&Token::Plus));
+/// assert_eq!(precedence(&Token::If), None);
+/// ```
+pub fn keyword<T>(word: &str) -> Option<&'static T>
+where
+    T: AssocStatic<Table<&'static str, T>, KeywordsTag> + 'static,
+{
+    T::get_static().get(&word)
+}
+
+/// Returns the precedence of 'token' from the precedence table of its type.
+pub fn precedence<T>(token: &T) -> Option<u8>
+where
+    T: AssocStatic<Table<T, u8>, PrecedenceTag> + PartialEq + 'static,
+{
+    T::get_static().get(token).copied()
+}
+
+/// Associates a keyword table with a token type, used by `keyword()`.
+#[macro_export]
+macro_rules! assoc_keywords {
+    ($T:ty { $($WORD:literal => $TOKEN:expr),* $(,)? }) => {
+        $crate::assoc_static!(
+            $crate::KeywordsTag:$T,
+            $crate::Table<&'static str, $T> = $crate::Table(&[$(($WORD, $TOKEN)),*])
+        );
+    };
+}
+
+/// Associates an operator precedence table with a token type, used by `precedence()`.
+#[macro_export]
+macro_rules! assoc_precedence {
+    ($T:ty { $($TOKEN:expr => $PRECEDENCE:expr),* $(,)? }) => {
+        $crate::assoc_static!(
+            $crate::PrecedenceTag:$T,
+            $crate::Table<$T, u8> = $crate::Table(&[$(($TOKEN, $PRECEDENCE)),*])
+        );
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{keyword, precedence, Table};
+
+    #[derive(Debug, PartialEq)]
+    enum Op {
+        Add,
+        Mul,
+        Pow,
+    }
+
+    assoc_keywords!(Op { "add" => Op::Add, "mul" => Op::Mul });
+    assoc_precedence!(Op { Op::Add => 1, Op::Mul => 2, Op::Pow => 3 });
+
+    #[test]
+    fn lookups() {
+        assert_eq!(keyword::<Op>("mul"), Some(&Op::Mul));
+        assert_eq!(keyword::<Op>("pow"), None);
+        assert_eq!(precedence(&Op::Pow), Some(3));
+        assert_eq!(Table::<u8, u8>(&[]).get(&0), None);
+    }
+}
@@ -0,0 +1,192 @@
+/// Marker trait for tag types, implemented by types declared with `tags!()`.
+/// Tags are not required to implement it, but doing so gives each tag a name that is unique
+/// across crates.
+pub trait Tag {
+    /// The full path of the tag type, prefixed with the module path where it was declared.
+    const NAME: &'static str;
+}
+
+impl Tag for () {
+    const NAME: &'static str = "()";
+}
+
+/// Declares zero-sized marker types to be used as tags.
+///
+/// Each tag implements `Tag` with a name prefixed by the module path of the declaration, so
+/// tags with the same name in different crates or modules stay distinguishable. Keeping
+/// tags in a dedicated (private) module documents their meaning in one place.
+///
+/// A tag declared as 'NAME: TARGET' also gets an inherent `NAME::of::<T>()` accessor which
+/// returns the 'TARGET' associated with this tag to 'T', for code that is organized around
+/// tags rather than around the types things are associated to.
+///
+/// ```
+/// use crate::assoc_static::*;
+///
+/// mod tags {
+///     assoc_static::tags! {
+///         /// Greeting text
+///         pub Hello;
+///         /// Human readable type description
+///         pub ExplainType: &'static str;
+///     }
+/// }
+///
+/// struct Example;
+/// assoc_static!(tags::Hello:Example, &'static str = "Hello World!");
+/// assoc_static!(tags::ExplainType:Example, &'static str = "This is 'struct Example'");
+///
+/// assert!(<tags::Hello as Tag>::NAME.ends_with("::tags::Hello"));
+/// assert_eq!(AssocStatic::<_, tags::Hello>::assoc(&Example), &"Hello World!");
+/// assert_eq!(tags::ExplainType::of::<Example>(), &"This is 'struct Example'");
+/// ```
+#[macro_export]
+macro_rules! tags {
+    ($($(#[$meta:meta])* $vis:vis $TAG:ident $(: $TARGET:ty)?;)*) => {
+        $(
+            $(#[$meta])*
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+            $vis struct $TAG;
+
+            impl $crate::Tag for $TAG {
+                const NAME: &'static str = concat!(module_path!(), "::", stringify!($TAG));
+            }
+
+            $(
+                impl $TAG {
+                    /// Returns the static object associated with this tag to 'T'.
+                    #[allow(dead_code)]
+                    $vis fn of<T>() -> &'static $TARGET
+                    where
+                        T: $crate::AssocStatic<$TARGET, $TAG> + ?Sized,
+                    {
+                        T::get_static()
+                    }
+                }
+            )?
+        )*
+    };
+}
+
+tags! {
+    /// Tag for the canonical default instance of a type, associated with
+    /// `#[derive(AssocDefault)]`.
+    pub DefaultsTag;
+}
+
+/// Lists the tags that are attached to a type, implemented with `has_tags!()`.
+pub trait HasTags {
+    /// The `Tag::NAME`s of all tags attached to this type.
+    const TAGS: &'static [&'static str];
+
+    /// Returns true when 'TAG' is listed in `TAGS`.
+    fn has_tag<TAG: Tag>() -> bool {
+        Self::TAGS.contains(&TAG::NAME)
+    }
+}
+
+/// Implements `HasTags` for a type. Each listed tag must implement `Tag`, usually by declaring
+/// it with `tags!()`, and the type must have an association with it, this is checked at
+/// compile time. A tag the type has several associations with is listed as 'TAG => TARGET'
+/// naming one of them.
+///
+/// ```
+/// use crate::assoc_static::*;
+///
+/// tags! {
+///     pub Hello;
+///     pub ExplainType;
+///     pub Unused;
+/// }
+///
+/// struct Example;
+/// assoc_static!(Hello:Example, &'static str = "Hello World!");
+/// assoc_static!(ExplainType:Example, &'static str = "This is 'struct Example'");
+/// assoc_static!(ExplainType:Example, u32 = 42);
+/// has_tags!(Example: Hello, ExplainType => u32);
+///
+/// assert_eq!(Example::TAGS.len(), 2);
+/// assert!(Example::has_tag::<Hello>());
+/// assert!(!Example::has_tag::<Unused>());
+/// ```
+///
+/// ```compile_fail
+/// use crate::assoc_static::*;
+///
+/// tags! {
+///     pub Unused;
+/// }
+///
+/// struct Example;
+/// has_tags!(Example: Unused);
+/// ```
+#[macro_export]
+macro_rules! has_tags {
+    ($T:ty: $($TAG:ty $(=> $TARGET:ty)?),* $(,)?) => {
+        impl $crate::HasTags for $T {
+            const TAGS: &'static [&'static str] = &[$(<$TAG as $crate::Tag>::NAME),*];
+        }
+        $(const _: () = $crate::has_tags!(@assert $T, $TAG $(, $TARGET)?);)*
+    };
+    (@assert $T:ty, $TAG:ty) => {
+        $crate::assert_assoc_helper::<$T, _, $TAG>()
+    };
+    (@assert $T:ty, $TAG:ty, $TARGET:ty) => {
+        $crate::assert_assoc_helper::<$T, $TARGET, $TAG>()
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{assoc_static, AssocStatic, HasTags, Tag};
+
+    mod tags {
+        tags! {
+            pub First;
+            pub(crate) Second: u32;
+        }
+    }
+
+    struct TestType;
+    assoc_static!(tags::First:TestType, u32 = 1);
+    assoc_static!(tags::Second:TestType, u32 = 2);
+    assoc_static!(TestType, u32 = 3);
+
+    #[test]
+    fn names() {
+        assert_eq!(tags::First::NAME, "assoc_static::tags::tests::tags::First");
+        assert_eq!(
+            tags::Second::NAME,
+            "assoc_static::tags::tests::tags::Second"
+        );
+        assert_eq!(<() as Tag>::NAME, "()");
+    }
+
+    #[test]
+    fn lookup() {
+        assert_eq!(
+            *<TestType as AssocStatic<u32, tags::First>>::get_static(),
+            1
+        );
+        assert_eq!(
+            *<TestType as AssocStatic<u32, tags::Second>>::get_static(),
+            2
+        );
+    }
+
+    has_tags!(TestType: tags::First, tags::Second, ());
+
+    #[test]
+    fn has_tags() {
+        assert_eq!(
+            TestType::TAGS,
+            &[tags::First::NAME, tags::Second::NAME, "()"]
+        );
+        assert!(TestType::has_tag::<()>());
+    }
+
+    #[test]
+    fn tag_of() {
+        assert_eq!(*tags::Second::of::<TestType>(), 2);
+    }
+}
@@ -0,0 +1,72 @@
+use std::cell::UnsafeCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// A static object that hands out a mutable reference exactly once, implemented with
+/// `assoc_static!(TAG:T, TARGET = INIT; take)`. These associations are not available
+/// through `AssocStatic` because shared references would alias the mutable one.
+///
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Uart;
+/// struct Registers {
+///     baud: u32,
+/// }
+/// assoc_static!(Uart, Registers = Registers { baud: 9600 }; take);
+///
+/// let registers = <Uart as AssocTake<Registers>>::take_static().unwrap();
+/// registers.baud = 115200;
+/// assert!(<Uart as AssocTake<Registers>>::take_static().is_none());
+/// ```
+pub trait AssocTake<T: 'static, TAG = ()> {
+    /// Returns the associated static object on the first call and `None` afterwards.
+    fn take_static() -> Option<&'static mut T>;
+}
+
+/// Only a helper, needs to be public because of the macro.
+#[doc(hidden)]
+pub struct TakeCell<T> {
+    taken: AtomicBool,
+    value: UnsafeCell<T>,
+}
+
+// SAFETY: the value is only ever accessed through the single reference handed out by 'take',
+// which may be sent to another thread.
+unsafe impl<T: Send> Sync for TakeCell<T> {}
+
+impl<T> TakeCell<T> {
+    pub const fn new(value: T) -> Self {
+        TakeCell {
+            taken: AtomicBool::new(false),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    // the 'taken' flag guarantees that at most one mutable reference exists
+    #[allow(clippy::mut_from_ref)]
+    pub fn take(&'static self) -> Option<&'static mut T> {
+        if self.taken.swap(true, Ordering::AcqRel) {
+            None
+        } else {
+            // SAFETY: 'taken' was false, this is the only reference ever created.
+            Some(unsafe { &mut *self.value.get() })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{assoc_static, AssocTake};
+
+    struct TestType;
+    struct TestTag;
+    assoc_static!(TestTag:TestType, Vec<u32> = Vec::new(); take);
+
+    #[test]
+    fn take_once() {
+        let value = <TestType as AssocTake<Vec<u32>, TestTag>>::take_static().unwrap();
+        value.push(1);
+        assert_eq!(value, &[1]);
+        assert!(<TestType as AssocTake<Vec<u32>, TestTag>>::take_static().is_none());
+    }
+}
@@ -0,0 +1,194 @@
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+use crate::AssocStatic;
+
+crate::tags! {
+    /// Tag for the validation `Rules` of a type.
+    pub RulesTag;
+}
+
+/// The validation rules of a type, associated under the `RulesTag`.
+pub type Rules<V> = &'static [Rule<V>];
+
+/// A validation rule for values of type 'V'.
+#[derive(Debug)]
+pub enum Rule<V: ?Sized + 'static> {
+    /// Minimum length in bytes or elements
+    MinLen(usize),
+    /// Maximum length in bytes or elements
+    MaxLen(usize),
+    /// Minimum numeric value
+    Min(i128),
+    /// Maximum numeric value
+    Max(i128),
+    /// A custom predicate and the message reported when it fails
+    Check(fn(&V) -> bool, &'static str),
+}
+
+/// Values rules can be checked against. Length rules fail on values without a length and
+/// range rules on non-numeric values.
+pub trait Subject {
+    /// Returns the length of the value.
+    fn length(&self) -> Option<usize> {
+        None
+    }
+
+    /// Returns the numeric value.
+    fn number(&self) -> Option<i128> {
+        None
+    }
+}
+
+impl Subject for str {
+    fn length(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl Subject for String {
+    fn length(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl Subject for &str {
+    fn length(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<T> Subject for Vec<T> {
+    fn length(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+macro_rules! numeric_subject {
+    ($($T:ty),*) => {
+        $(impl Subject for $T {
+            fn number(&self) -> Option<i128> {
+                Some(*self as i128)
+            }
+        })*
+    };
+}
+numeric_subject!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+
+/// The first rule a value violated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    /// Name of the type whose rules were checked
+    pub type_name: &'static str,
+    /// Description of the violated rule
+    pub rule: String,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid {}: {}", self.type_name, self.rule)
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+impl<V: Subject + ?Sized> Rule<V> {
+    fn violation(&self, value: &V) -> Option<String> {
+        let ok = match *self {
+            Rule::MinLen(min) => value.length().is_some_and(|len| len >= min),
+            Rule::MaxLen(max) => value.length().is_some_and(|len| len <= max),
+            Rule::Min(min) => value.number().is_some_and(|n| n >= min),
+            Rule::Max(max) => value.number().is_some_and(|n| n <= max),
+            Rule::Check(check, _) => check(value),
+        };
+        (!ok).then(|| match *self {
+            Rule::MinLen(min) => format!("shorter than {min}"),
+            Rule::MaxLen(max) => format!("longer than {max}"),
+            Rule::Min(min) => format!("less than {min}"),
+            Rule::Max(max) => format!("greater than {max}"),
+            Rule::Check(_, message) => message.to_string(),
+        })
+    }
+}
+
+/// A value that passed the validation rules associated with 'T'.
+///
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Username;
+/// assoc_static!(RulesTag:Username, Rules<String> = &[
+///     Rule::MinLen(3),
+///     Rule::MaxLen(16),
+///     Rule::Check(|name| name.chars().all(char::is_alphanumeric), "not alphanumeric"),
+/// ]);
+///
+/// let name = Validated::<Username, String>::new("alice".into()).unwrap();
+/// assert_eq!(name.as_str(), "alice");
+/// let error = Validated::<Username, String>::new("a!ice".into()).unwrap_err();
+/// assert!(error.to_string().ends_with("Username: not alphanumeric"));
+/// ```
+pub struct Validated<T: ?Sized, V>(V, PhantomData<fn() -> T>);
+
+impl<T, V> Validated<T, V>
+where
+    T: AssocStatic<Rules<V>, RulesTag> + ?Sized,
+    V: Subject + 'static,
+{
+    /// Checks 'value' against the rules of 'T'.
+    pub fn new(value: V) -> Result<Self, ValidationError> {
+        match T::get_static()
+            .iter()
+            .find_map(|rule| rule.violation(&value))
+        {
+            Some(rule) => Err(ValidationError {
+                type_name: std::any::type_name::<T>(),
+                rule,
+            }),
+            None => Ok(Validated(value, PhantomData)),
+        }
+    }
+}
+
+impl<T: ?Sized, V> Validated<T, V> {
+    /// Returns the validated value.
+    pub fn into_inner(self) -> V {
+        self.0
+    }
+}
+
+impl<T: ?Sized, V> Deref for Validated<T, V> {
+    type Target = V;
+
+    fn deref(&self) -> &V {
+        &self.0
+    }
+}
+
+impl<T: ?Sized, V: fmt::Debug> fmt::Debug for Validated<T, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Rule, Rules, Validated};
+    use crate::{assoc_static, RulesTag};
+
+    struct Percent;
+    assoc_static!(RulesTag:Percent, Rules<u8> = &[Rule::Min(0), Rule::Max(100)]);
+
+    struct Named;
+    assoc_static!(RulesTag:Named, Rules<u8> = &[Rule::MinLen(1)]);
+
+    #[test]
+    fn ranges() {
+        assert_eq!(*Validated::<Percent, u8>::new(42).unwrap(), 42);
+        let error = Validated::<Percent, u8>::new(101).unwrap_err();
+        assert_eq!(error.rule, "greater than 100");
+        // length rules do not apply to numbers
+        assert!(Validated::<Named, u8>::new(1).is_err());
+    }
+}
@@ -0,0 +1,104 @@
+use std::sync::{Mutex, PoisonError, RwLock, RwLockReadGuard};
+
+use crate::AssocStatic;
+
+type Callback<T> = Box<dyn Fn(&T) + Send + Sync>;
+
+/// A mutable static object which notifies subscribers after each write, so values derived
+/// from it can be invalidated. Callbacks run on the writing thread with the new value, they
+/// must not write to or subscribe to the same object.
+///
+/// ```
+/// use crate::assoc_static::*;
+/// use std::sync::atomic::{AtomicU32, Ordering};
+///
+/// struct Server;
+/// assoc_static!(Server, Watched<u32> = Watched::new(8080));
+///
+/// static SEEN: AtomicU32 = AtomicU32::new(0);
+/// on_change::<Server, u32, ()>(|port| SEEN.store(*port, Ordering::Relaxed));
+///
+/// <Server as AssocStatic<Watched<u32>>>::get_static().set(8443);
+/// assert_eq!(SEEN.load(Ordering::Relaxed), 8443);
+/// assert_eq!(*<Server as AssocStatic<Watched<u32>>>::get_static().read(), 8443);
+/// ```
+pub struct Watched<T> {
+    value: RwLock<T>,
+    callbacks: Mutex<Vec<Callback<T>>>,
+}
+
+impl<T> Watched<T> {
+    /// Creates a watched object holding 'value'.
+    pub const fn new(value: T) -> Self {
+        Watched {
+            value: RwLock::new(value),
+            callbacks: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Locks the value for reading.
+    pub fn read(&self) -> RwLockReadGuard<'_, T> {
+        self.value.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Replaces the value and notifies the subscribers.
+    pub fn set(&self, value: T) {
+        self.update(|old| *old = value);
+    }
+
+    /// Modifies the value with 'f' and notifies the subscribers.
+    pub fn update(&self, f: impl FnOnce(&mut T)) {
+        f(&mut self.value.write().unwrap_or_else(PoisonError::into_inner));
+        let value = self.read();
+        let callbacks = self
+            .callbacks
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        callbacks.iter().for_each(|callback| callback(&value));
+    }
+
+    /// Calls 'callback' after each write.
+    pub fn on_change(&self, callback: impl Fn(&T) + Send + Sync + 'static) {
+        self.callbacks
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(Box::new(callback));
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for Watched<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Watched").field(&*self.read()).finish()
+    }
+}
+
+/// Calls 'callback' after each write to the `Watched` object associated with 'V'.
+pub fn on_change<V, T, TAG>(callback: impl Fn(&T) + Send + Sync + 'static)
+where
+    V: AssocStatic<Watched<T>, TAG> + ?Sized,
+    T: 'static,
+{
+    V::get_static().on_change(callback);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use super::Watched;
+
+    #[test]
+    fn notifies_each_write() {
+        static WATCHED: Watched<Vec<u32>> = Watched::new(Vec::new());
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        WATCHED.on_change(move |value| {
+            counter.fetch_add(value.len(), Ordering::Relaxed);
+        });
+        WATCHED.update(|value| value.push(1));
+        WATCHED.update(|value| value.push(2));
+        assert_eq!(calls.load(Ordering::Relaxed), 3);
+        assert_eq!(*WATCHED.read(), [1, 2]);
+    }
+}
@@ -0,0 +1,100 @@
+use std::sync::OnceLock;
+use std::time::Duration;
+
+crate::tags! {
+    /// Tag for the `Wavetable` of an instrument type.
+    pub WavetableTag: Wavetable;
+}
+
+/// Encoding of the embedded sample data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SampleFormat {
+    /// Signed 16 bit little endian
+    Pcm16,
+    /// 32 bit float little endian
+    F32,
+}
+
+/// Embedded mono sample data which is decoded into `f32` samples on first use.
+///
+/// ```
+/// use crate::assoc_static::*;
+///
+/// struct Click;
+/// // usually 'include_bytes!("click.raw")'
+/// const CLICK: &[u8] = &[0x00, 0x00, 0xff, 0x7f, 0x00, 0x80];
+/// assoc_static!(
+///     WavetableTag:Click,
+///     Wavetable = Wavetable::new(CLICK, SampleFormat::Pcm16, 48000)
+/// );
+///
+/// let click = WavetableTag::of::<Click>();
+/// assert_eq!(click.samples().len(), 3);
+/// assert_eq!(click.samples()[2], -1.0);
+/// assert_eq!(click.sample_rate(), 48000);
+/// ```
+#[derive(Debug)]
+pub struct Wavetable {
+    data: &'static [u8],
+    format: SampleFormat,
+    sample_rate: u32,
+    samples: OnceLock<Box<[f32]>>,
+}
+
+impl Wavetable {
+    /// Creates a wavetable from embedded 'data'. Trailing bytes of an incomplete sample are
+    /// ignored.
+    pub const fn new(data: &'static [u8], format: SampleFormat, sample_rate: u32) -> Self {
+        Wavetable {
+            data,
+            format,
+            sample_rate,
+            samples: OnceLock::new(),
+        }
+    }
+
+    /// Returns the samples, decoding them on the first call.
+    pub fn samples(&self) -> &[f32] {
+        self.samples.get_or_init(|| match self.format {
+            SampleFormat::Pcm16 => self
+                .data
+                .chunks_exact(2)
+                .map(|bytes| i16::from_le_bytes([bytes[0], bytes[1]]) as f32 / 32768.0)
+                .collect(),
+            SampleFormat::F32 => self
+                .data
+                .chunks_exact(4)
+                .map(|bytes| f32::from_le_bytes(bytes.try_into().expect("4 bytes")))
+                .collect(),
+        })
+    }
+
+    /// Returns the sample rate in Hz.
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Returns the play time of the samples.
+    pub fn duration(&self) -> Duration {
+        let bytes = match self.format {
+            SampleFormat::Pcm16 => 2,
+            SampleFormat::F32 => 4,
+        };
+        let samples = (self.data.len() / bytes) as f64;
+        Duration::from_secs_f64(samples / self.sample_rate.max(1) as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SampleFormat, Wavetable};
+    use std::time::Duration;
+
+    #[test]
+    fn decode_f32() {
+        static DATA: [u8; 9] = [0, 0, 128, 63, 0, 0, 0, 191, 0];
+        let table = Wavetable::new(&DATA, SampleFormat::F32, 2);
+        assert_eq!(table.samples(), &[1.0, -0.5]);
+        assert_eq!(table.duration(), Duration::from_secs(1));
+    }
+}
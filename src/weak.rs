@@ -0,0 +1,77 @@
+use std::sync::{Arc, Mutex, PoisonError, Weak};
+
+crate::tags! {
+    /// Tag for the `WeakSlot` of a type.
+    pub WeakSlotTag;
+}
+
+/// Holds a shared resource only while something else keeps it alive. The first
+/// `get_or_create()` creates the resource, later calls share it and once the last `Arc` is
+/// dropped the resource is dropped as well, to be created again on the next call.
+///
+/// ```
+/// use crate::assoc_static::*;
+/// use std::sync::Arc;
+///
+/// struct Database;
+/// struct Connection(u32);
+/// assoc_static!(WeakSlotTag:Database, WeakSlot<Connection> = WeakSlot::new());
+///
+/// let slot = <Database as AssocStatic<WeakSlot<Connection>, WeakSlotTag>>::get_static();
+/// let first = slot.get_or_create(|| Arc::new(Connection(1)));
+/// let second = slot.get_or_create(|| Arc::new(Connection(2)));
+/// assert!(Arc::ptr_eq(&first, &second));
+///
+/// drop((first, second));
+/// assert!(slot.get().is_none());
+/// assert_eq!(slot.get_or_create(|| Arc::new(Connection(3))).0, 3);
+/// ```
+#[derive(Debug, Default)]
+pub struct WeakSlot<T>(Mutex<Weak<T>>);
+
+impl<T> WeakSlot<T> {
+    /// Creates an empty slot.
+    pub const fn new() -> Self {
+        WeakSlot(Mutex::new(Weak::new()))
+    }
+
+    /// Returns the resource when it is alive.
+    pub fn get(&self) -> Option<Arc<T>> {
+        self.lock().upgrade()
+    }
+
+    /// Returns the resource, creating it with 'create' when it is not alive. 'create' is
+    /// called with the slot locked, concurrent callers wait for it instead of creating
+    /// their own resource.
+    pub fn get_or_create(&self, create: impl FnOnce() -> Arc<T>) -> Arc<T> {
+        let mut slot = self.lock();
+        match slot.upgrade() {
+            Some(resource) => resource,
+            None => {
+                let resource = create();
+                *slot = Arc::downgrade(&resource);
+                resource
+            }
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Weak<T>> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::WeakSlot;
+
+    #[test]
+    fn recreate() {
+        static SLOT: WeakSlot<u32> = WeakSlot::new();
+        let first = SLOT.get_or_create(|| Arc::new(1));
+        assert_eq!(*SLOT.get_or_create(|| Arc::new(2)), 1);
+        drop(first);
+        assert_eq!(*SLOT.get_or_create(|| Arc::new(3)), 3);
+    }
+}
@@ -0,0 +1,73 @@
+//! Checks that the accessor of a plain association compiles down to returning the address
+//! of its static. The crate and a probe using it are compiled with the same rustc that runs
+//! the tests, the probe is emitted as optimized LLVM IR and inspected.
+
+use std::path::Path;
+use std::process::Command;
+
+const PROBE: &str = r#"
+use assoc_static::*;
+
+pub struct Probe;
+assoc_static!(Probe, [u32; 4] = [1, 2, 3, 4]);
+
+#[no_mangle]
+#[inline(never)]
+pub fn assoc_static_probe() -> &'static [u32; 4] {
+    <Probe as AssocStatic<[u32; 4]>>::get_static()
+}
+"#;
+
+fn rustc(out_dir: &Path, args: &[&str]) {
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".into());
+    let status = Command::new(rustc)
+        .args(["--edition", "2021", "-C", "opt-level=3", "--out-dir"])
+        .arg(out_dir)
+        .args(args)
+        .status()
+        .expect("running rustc");
+    assert!(status.success(), "rustc {args:?} failed");
+}
+
+#[test]
+fn accessor_is_a_symbol_address() {
+    let out_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("codegen");
+    std::fs::create_dir_all(&out_dir).unwrap();
+    let lib = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/lib.rs");
+    rustc(
+        &out_dir,
+        &[
+            "--crate-type=rlib",
+            "--crate-name=assoc_static",
+            lib.to_str().unwrap(),
+        ],
+    );
+    let probe = out_dir.join("probe.rs");
+    std::fs::write(&probe, PROBE).unwrap();
+    let rlib = out_dir.join("libassoc_static.rlib");
+    rustc(
+        &out_dir,
+        &[
+            "--crate-type=lib",
+            "--emit=llvm-ir",
+            "--extern",
+            &format!("assoc_static={}", rlib.display()),
+            probe.to_str().unwrap(),
+        ],
+    );
+
+    let ir = std::fs::read_to_string(out_dir.join("probe.ll")).unwrap();
+    let body: Vec<&str> = ir
+        .lines()
+        .skip_while(|line| !(line.starts_with("define") && line.contains("@assoc_static_probe(")))
+        .skip(1)
+        .take_while(|line| *line != "}")
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.ends_with(':'))
+        .collect();
+    assert_eq!(body.len(), 1, "accessor is more than a return: {body:#?}");
+    assert!(
+        body[0].starts_with("ret ptr @"),
+        "accessor does not return a symbol: {body:#?}"
+    );
+}
@@ -0,0 +1,80 @@
+#![cfg(feature = "derive")]
+use assoc_static::*;
+
+pub struct Inner;
+struct Hello;
+assoc_static!(Inner, [u32, &'static str] = (42, "inner"));
+assoc_static!(Hello:Inner, Vec<u8> = Vec::new());
+
+#[derive(AssocDelegate)]
+#[assoc_delegate(u32, &'static str)]
+#[assoc_delegate(Hello: Vec<u8>)]
+pub struct Wrapper(pub Inner);
+
+#[test]
+fn delegate() {
+    let wrapper = Wrapper(Inner);
+    assert_eq!(*AssocStatic::<u32>::assoc(&wrapper), 42);
+    assert_eq!(*AssocStatic::<&str>::assoc(&wrapper), "inner");
+    assert!(AssocStatic::<Vec<u8>, Hello>::assoc(&wrapper).is_empty());
+    assert_eq!(
+        <Wrapper as AssocStatic<u32>>::descriptor(),
+        <Inner as AssocStatic<u32>>::descriptor()
+    );
+}
+
+#[derive(Default, AssocDefault)]
+struct Settings {
+    name: String,
+    level: u8,
+}
+
+#[test]
+fn default() {
+    let settings = <Settings as AssocStatic<Settings, DefaultsTag>>::get_static();
+    assert!(settings.name.is_empty());
+    assert_eq!(settings.level, 0);
+    assert!(std::ptr::eq(
+        settings,
+        AssocStatic::<Settings, DefaultsTag>::assoc(&Settings::default())
+    ));
+}
+
+#[derive(AssocMessage)]
+#[assoc_message(magic = 0x1234_5678, id = 3)]
+#[assoc_message(min_len = 1, max_len = 2)]
+struct Message;
+
+#[test]
+fn message() {
+    assert_eq!(
+        *MessageTag::of::<Message>(),
+        MessageInfo {
+            magic: 0x1234_5678,
+            id: 3,
+            min_len: 1,
+            max_len: 2
+        }
+    );
+}
+
+struct Config;
+assoc_static!(Config, &'static str = "default"; sync(mutex));
+assoc_snapshots!(static STATE = [():Config => std::sync::Mutex<&'static str>]);
+
+fn host() -> std::sync::MutexGuard<'static, &'static str> {
+    <Config as AssocMutex<&str>>::lock_static()
+}
+
+#[assoc_test(STATE)]
+fn isolated_write() {
+    assert_eq!(*host(), "default");
+    *host() = "written";
+}
+
+#[assoc_test(STATE)]
+fn isolated_result() -> Result<(), String> {
+    assert_eq!(*host(), "default");
+    *host() = "result";
+    Ok(())
+}
@@ -10,3 +10,57 @@ fn type_coherency() {
     assoc_static!(TestType:i32, &'static str = "This is i32");
     assert_eq!(i32::get_static(), &"This is i32");
 }
+
+#[test]
+fn unsized_types() {
+    struct Describe;
+
+    assoc_static!(Describe:str, &'static str = "string slice");
+    assoc_static!(Describe:[u8], &'static str = "byte slice");
+    assoc_static!(Describe:std::path::Path, &'static str = "path");
+
+    fn describe<T: AssocStatic<&'static str, Describe> + ?Sized>(this: &T) -> &'static str {
+        this.assoc()
+    }
+
+    assert_eq!(describe("hello"), "string slice");
+    assert_eq!(describe(&b"hello"[..]), "byte slice");
+    assert_eq!(describe(std::path::Path::new("/tmp")), "path");
+}
+
+#[test]
+fn tuple_tags() {
+    struct TestType;
+    struct Locale;
+    struct Channel;
+
+    assoc_static!((Locale, Channel):TestType, u32 = 1);
+    assoc_static!((Channel, Locale):TestType, u32 = 2);
+    assoc_static!(Locale:TestType, u32 = 3);
+
+    assert_eq!(
+        *<TestType as AssocStatic<u32, (Locale, Channel)>>::get_static(),
+        1
+    );
+    assert_eq!(
+        *<TestType as AssocStatic<u32, (Channel, Locale)>>::get_static(),
+        2
+    );
+    assert_eq!(*<TestType as AssocStatic<u32, Locale>>::get_static(), 3);
+}
+
+#[test]
+fn feature_init() {
+    struct TestType;
+
+    assoc_static!(TestType, &'static str = cfg {
+        feature = "delegate" => "with delegate",
+        _ => "without delegate",
+    });
+
+    if cfg!(feature = "delegate") {
+        assert_eq!(TestType::get_static(), &"with delegate");
+    } else {
+        assert_eq!(TestType::get_static(), &"without delegate");
+    }
+}